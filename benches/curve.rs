@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{black_box, Criterion};
+use noise::{Curve, NoiseFn, Perlin};
+
+criterion_group!(curve, bench_curve_exact_64x64, bench_curve_baked_64x64);
+criterion_main!(curve);
+
+fn build_curve() -> Curve<f64, Perlin, 2> {
+    // A terrain-style curve with many control points, where the search through
+    // the control points dominates the cost of exact evaluation.
+    (0..=32).fold(Curve::new(Perlin::new(0)), |curve, i| {
+        let input = i as f64 / 16.0 - 1.0;
+        curve.add_control_point(input, input * input * input.signum())
+    })
+}
+
+fn bench_curve(c: &mut Criterion, name: &str, curve: Curve<f64, Perlin, 2>) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(curve.get([x as f64 * 0.1, y as f64 * 0.1]));
+                }
+            }
+        })
+    });
+}
+
+fn bench_curve_exact_64x64(c: &mut Criterion) {
+    bench_curve(c, "curve exact (64x64)", build_curve());
+}
+
+fn bench_curve_baked_64x64(c: &mut Criterion) {
+    bench_curve(c, "curve baked (64x64)", build_curve().bake(1024));
+}
@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{black_box, Criterion};
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+criterion_group!(fbm, bench_fbm_exact_64x64, bench_fbm_approximate_64x64);
+criterion_main!(fbm);
+
+fn bench_fbm(c: &mut Criterion, name: &str, fbm: Fbm<Perlin>) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(fbm.get([x as f64 * 0.1, y as f64 * 0.1]));
+                }
+            }
+        })
+    });
+}
+
+fn bench_fbm_exact_64x64(c: &mut Criterion) {
+    bench_fbm(c, "fbm exact (64x64)", Fbm::new(0).set_octaves(6));
+}
+
+fn bench_fbm_approximate_64x64(c: &mut Criterion) {
+    bench_fbm(
+        c,
+        "fbm approximate (64x64)",
+        Fbm::new(0).set_octaves(6).set_approximate(true),
+    );
+}
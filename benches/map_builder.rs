@@ -0,0 +1,35 @@
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{black_box, Criterion};
+use noise::{
+    utils::{NoiseMapBuilder, PlaneMapBuilder},
+    Fbm, MultiFractal, Perlin, Turbulence, Worley,
+};
+
+criterion_group!(
+    map_builder,
+    bench_plane_row_major_256x256,
+    bench_plane_tiled_256x256
+);
+criterion_main!(map_builder);
+
+fn bench_plane(c: &mut Criterion, name: &str, tile_size: Option<usize>) {
+    let source = Turbulence::<_, Perlin>::new(Fbm::<Worley>::new(0).set_octaves(4));
+    let builder = PlaneMapBuilder::<_, 2>::new(&source)
+        .set_size(256, 256)
+        .set_x_bounds(-4.0, 4.0)
+        .set_y_bounds(-4.0, 4.0)
+        .set_tile_size(tile_size);
+
+    c.bench_function(name, |b| b.iter(|| black_box(builder.build())));
+}
+
+fn bench_plane_row_major_256x256(c: &mut Criterion) {
+    bench_plane(c, "plane map row-major (256x256)", None);
+}
+
+fn bench_plane_tiled_256x256(c: &mut Criterion) {
+    bench_plane(c, "plane map tiled 64x64 (256x256)", Some(64));
+}
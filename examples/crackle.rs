@@ -0,0 +1,31 @@
+//! An example of using the Crackle noise function
+
+extern crate noise;
+
+use noise::{utils::*, Crackle, MultiFractal};
+
+mod utils;
+
+fn main() {
+    let crackle = Crackle::default();
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::<_, 2>::new(&crackle)
+            .set_size(512, 512)
+            .set_x_bounds(-4.0, 4.0)
+            .set_y_bounds(-4.0, 4.0)
+            .build(),
+        "crackle.png",
+    );
+
+    let crackle = crackle.set_octaves(6).set_persistence(0.7);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::<_, 3>::new(crackle)
+            .set_size(512, 512)
+            .set_x_bounds(-4.0, 4.0)
+            .set_y_bounds(-4.0, 4.0)
+            .build(),
+        "crackle_detailed.png",
+    );
+}
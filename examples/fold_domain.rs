@@ -0,0 +1,18 @@
+extern crate noise;
+
+use noise::{utils::*, FoldDomain, Perlin};
+
+mod utils;
+
+fn main() {
+    let perlin = Perlin::default();
+    let fold = FoldDomain::new(perlin).set_period(0.5);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::<_, 2>::new(fold)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(-2.0, 2.0)
+            .build(),
+        "fold_domain.png",
+    );
+}
@@ -0,0 +1,19 @@
+//! An example of using hexagonal value noise
+
+extern crate noise;
+
+use noise::{core::hex_value::hex_value_2d, permutationtable::PermutationTable, utils::*};
+
+mod utils;
+
+fn main() {
+    let hasher = PermutationTable::new(0);
+    utils::write_example_to_file(
+        &PlaneMapBuilder::<_, 2>::new_fn(|point| hex_value_2d(point.into(), &hasher))
+            .set_size(1024, 1024)
+            .set_x_bounds(-5.0, 5.0)
+            .set_y_bounds(-5.0, 5.0)
+            .build(),
+        "hex value 2d.png",
+    );
+}
@@ -0,0 +1,18 @@
+extern crate noise;
+
+use noise::{utils::*, Fbm, Perlin, WarpDomain2};
+
+mod utils;
+
+fn main() {
+    let source = Fbm::<Perlin>::new(0);
+    let warp = WarpDomain2::new(source, Fbm::<Perlin>::new(1)).set_strength(4.0);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::<_, 2>::new(warp)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(-2.0, 2.0)
+            .build(),
+        "warp_domain.png",
+    );
+}
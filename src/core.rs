@@ -1,4 +1,5 @@
 pub mod checkerboard;
+pub mod hex_value;
 pub mod open_simplex;
 pub mod perlin;
 pub mod perlin_surflet;
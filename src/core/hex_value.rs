@@ -0,0 +1,55 @@
+use crate::{
+    math::{s_curve::quintic::Quintic, vectors::Vector2},
+    permutationtable::NoiseHasher,
+};
+
+// Skew factor for 2 dimensions, (sqrt(3) - 1) / 2. Skewing the input by this
+// factor maps the triangular lattice onto the integer square lattice, where
+// each square is split into two triangles along its main diagonal.
+const SKEW_FACTOR: f64 = 0.366_025_403_784_438_6;
+
+/// Value noise on a hexagonal (triangular) lattice.
+///
+/// The input point is located within a triangle of the lattice, and the
+/// hashed values at the three corners of that triangle are blended using the
+/// point's barycentric weights. The weights are smoothed with the quintic
+/// S-curve and renormalized, so the output has no creases along the triangle
+/// edges. Because each lattice point has six equidistant neighbors, the
+/// output is far more isotropic than square-lattice value noise.
+pub fn hex_value_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let skew = (point.x + point.y) * SKEW_FACTOR;
+    let skewed = point + skew;
+
+    let corner = Vector2::new(skewed.x.floor(), skewed.y.floor());
+    let frac = skewed - corner;
+    let corner = Vector2::new(corner.x as isize, corner.y as isize);
+
+    // Barycentric weights of the corners (0, 0), the middle corner, and (1, 1).
+    let (middle, weights) = if frac.x > frac.y {
+        (Vector2::new(1, 0), [1.0 - frac.x, frac.x - frac.y, frac.y])
+    } else {
+        (Vector2::new(0, 1), [1.0 - frac.y, frac.y - frac.x, frac.x])
+    };
+
+    let weights = weights.map(|weight: f64| weight.map_quintic());
+    let total: f64 = weights.iter().sum();
+
+    macro_rules! get(
+        ($offset:expr) => {
+            {
+                hasher.hash(&(corner + $offset).into_array()) as f64 / 255.0
+            }
+        }
+    );
+
+    let v0 = get!(Vector2::new(0, 0));
+    let v1 = get!(middle);
+    let v2 = get!(Vector2::new(1, 1));
+
+    let result = (v0 * weights[0] + v1 * weights[1] + v2 * weights[2]) / total;
+
+    result * 2.0 - 1.0
+}
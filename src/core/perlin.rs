@@ -8,6 +8,86 @@ use crate::{
 };
 use core::f64;
 
+/// Gradient directions used by [`perlin_1d`], indexed by the lattice hash
+/// masked to the length of the table. The components are 1, -1 or 0, which
+/// the scale factor is fitted to.
+const GRADIENTS_1D: [[f64; 1]; 2] = [[1.0], [-1.0]];
+
+/// Gradient directions used by [`perlin_2d`], indexed by the lattice hash
+/// masked to the length of the table. The components are 1, -1 or 0, which
+/// the scale factor is fitted to.
+#[rustfmt::skip]
+const GRADIENTS_2D: [[f64; 2]; 4] = [
+    [ 1.0,  1.0], // 0
+    [-1.0,  1.0], // 1
+    [ 1.0, -1.0], // 2
+    [-1.0, -1.0], // 3
+];
+
+/// Gradient directions used by [`perlin_3d`], indexed by the lattice hash
+/// masked to the length of the table. The components are 1, -1 or 0, which
+/// the scale factor is fitted to. Some directions appear twice, as in Ken
+/// Perlin's improved noise.
+#[rustfmt::skip]
+const GRADIENTS_3D: [[f64; 3]; 16] = [
+    [ 1.0,  1.0,  0.0], // 0
+    [-1.0,  1.0,  0.0], // 1
+    [ 1.0, -1.0,  0.0], // 2
+    [-1.0, -1.0,  0.0], // 3
+    [ 1.0,  0.0,  1.0], // 4
+    [-1.0,  0.0,  1.0], // 5
+    [ 1.0,  0.0, -1.0], // 6
+    [-1.0,  0.0, -1.0], // 7
+    [ 0.0,  1.0,  1.0], // 8
+    [ 0.0, -1.0,  1.0], // 9
+    [ 0.0,  1.0, -1.0], // 10
+    [ 0.0, -1.0, -1.0], // 11
+    [ 1.0,  1.0,  0.0], // 12
+    [-1.0,  1.0,  0.0], // 13
+    [ 0.0, -1.0,  1.0], // 14
+    [ 0.0, -1.0, -1.0], // 15
+];
+
+/// Gradient directions used by [`perlin_4d`], indexed by the lattice hash
+/// masked to the length of the table. The components are 1, -1 or 0, which
+/// the scale factor is fitted to. Not every edge of the hypercube is
+/// represented, and some directions appear more than once.
+#[rustfmt::skip]
+const GRADIENTS_4D: [[f64; 4]; 32] = [
+    [ 1.0,  1.0,  1.0,  0.0], // 0
+    [-1.0,  1.0,  1.0,  0.0], // 1
+    [ 1.0, -1.0,  1.0,  0.0], // 2
+    [ 1.0,  1.0, -1.0,  0.0], // 3
+    [-1.0,  1.0, -1.0,  0.0], // 4
+    [ 1.0, -1.0, -1.0,  0.0], // 5
+    [ 1.0, -1.0, -1.0,  0.0], // 6
+    [ 1.0,  1.0,  0.0,  1.0], // 7
+    [-1.0,  1.0,  0.0,  1.0], // 8
+    [ 1.0, -1.0,  0.0,  1.0], // 9
+    [ 1.0,  1.0,  0.0, -1.0], // 10
+    [ 1.0,  1.0,  0.0, -1.0], // 11
+    [ 1.0,  1.0,  0.0, -1.0], // 12
+    [-1.0, -1.0,  0.0, -1.0], // 13
+    [ 1.0,  0.0,  1.0,  1.0], // 14
+    [-1.0,  0.0,  1.0,  1.0], // 15
+    [ 1.0,  0.0, -1.0,  1.0], // 16
+    [ 1.0,  0.0,  1.0, -1.0], // 17
+    [ 1.0,  0.0,  1.0, -1.0], // 18
+    [ 1.0,  0.0,  1.0, -1.0], // 19
+    [-1.0,  0.0, -1.0, -1.0], // 20
+    [ 0.0,  1.0,  1.0,  1.0], // 21
+    [ 0.0, -1.0,  1.0,  1.0], // 22
+    [ 0.0,  1.0, -1.0,  1.0], // 23
+    [ 0.0,  1.0, -1.0, -1.0], // 24
+    [ 0.0, -1.0, -1.0, -1.0], // 25
+    [ 0.0,  1.0, -1.0, -1.0], // 26
+    [ 0.0, -1.0, -1.0, -1.0], // 27
+    [ 1.0,  1.0,  1.0,  0.0], // 28
+    [ 1.0,  1.0,  0.0,  1.0], // 29
+    [ 1.0,  0.0,  1.0,  1.0], // 30
+    [ 0.0,  1.0,  1.0,  1.0], // 31
+];
+
 #[inline(always)]
 pub fn perlin_1d<NH>(point: f64, hasher: &NH) -> f64
 where
@@ -20,18 +100,14 @@ where
     // 1/(sqrt(N)/2), N=1 -> 2/sqrt(1) -> 2
     const SCALE_FACTOR: f64 = 2.0;
 
-    let corner = point as isize;
+    let corner = point.floor() as isize;
     let distance = point - corner as f64;
 
     macro_rules! call_gradient(
         ($x_offset:expr) => {
             {
                 let offset = distance - $x_offset as f64;
-                match hasher.hash(&[corner + $x_offset]) & 0b1 {
-                    0 =>  offset, // ( 1 )
-                    1 => -offset, // (-1 )
-                    _ => unreachable!(),
-                }
+                GRADIENTS_1D[hasher.hash(&[corner + $x_offset]) & 0b1][0] * offset
             }
         }
     );
@@ -70,13 +146,8 @@ where
                 let offset = Vector2::new($x, $y);
                 let point = distance - offset.numcast().unwrap();
 
-                match hasher.hash(&(corner + offset).into_array()) & 0b11 {
-                    0 =>  point.x + point.y, // ( 1,  1)
-                    1 => -point.x + point.y, // (-1,  1)
-                    2 =>  point.x - point.y, // ( 1, -1)
-                    3 => -point.x - point.y, // (-1, -1)
-                    _ => unreachable!(),
-                }
+                let gradient = GRADIENTS_2D[hasher.hash(&(corner + offset).into_array()) & 0b11];
+                gradient[0] * point.x + gradient[1] * point.y
             }
         }
     );
@@ -124,21 +195,8 @@ where
                 let offset = Vector3::new($x, $y, $z);
                 let point = distance - offset.numcast().unwrap();
 
-                match hasher.hash(&(corner + offset).into_array()) & 0b1111 {
-                    0  | 12 =>  point.x + point.y, // ( 1,  1,  0)
-                    1  | 13 => -point.x + point.y, // (-1,  1,  0)
-                    2       =>  point.x - point.y, // ( 1, -1,  0)
-                    3       => -point.x - point.y, // (-1, -1,  0)
-                    4       =>  point.x + point.z, // ( 1,  0,  1)
-                    5       => -point.x + point.z, // (-1,  0,  1)
-                    6       =>  point.x - point.z, // ( 1,  0, -1)
-                    7       => -point.x - point.z, // (-1,  0, -1)
-                    8       =>  point.y + point.z, // ( 0,  1,  1)
-                    9  | 14 => -point.y + point.z, // ( 0, -1,  1)
-                    10      =>  point.y - point.z, // ( 0,  1, -1)
-                    11 | 15 => -point.y - point.z, // ( 0, -1, -1)
-                    _ => unreachable!(),
-                }
+                let gradient = GRADIENTS_3D[hasher.hash(&(corner + offset).into_array()) & 0b1111];
+                gradient[0] * point.x + gradient[1] * point.y + gradient[2] * point.z
             }
         }
     );
@@ -193,37 +251,11 @@ where
                 let offset = Vector4::new($x, $y, $z, $w);
                 let point = distance - offset.numcast().unwrap();
 
-                match hasher.hash(&(corner + offset).into_array()) & 0b11111 {
-                    0  | 28 =>  point.x + point.y + point.z, // ( 1,  1,  1,  0)
-                    1       => -point.x + point.y + point.z, // (-1,  1,  1,  0)
-                    2       =>  point.x - point.y + point.z, // ( 1, -1,  1,  0)
-                    3       =>  point.x + point.y - point.z, // ( 1,  1, -1,  0)
-                    4       => -point.x + point.y - point.z, // (-1,  1, -1,  0)
-                    5       =>  point.x - point.y - point.z, // ( 1, -1, -1,  0)
-                    6       =>  point.x - point.y - point.z, // (-1, -1, -1,  0)
-                    7  | 29 =>  point.x + point.y + point.w, // ( 1,  1,  0,  1)
-                    8       => -point.x + point.y + point.w, // (-1,  1,  0,  1)
-                    9       =>  point.x - point.y + point.w, // ( 1, -1,  0,  1)
-                    10      =>  point.x + point.y - point.w, // ( 1,  1,  0, -1)
-                    11      =>  point.x + point.y - point.w, // (-1,  1,  0, -1)
-                    12      =>  point.x + point.y - point.w, // ( 1, -1,  0, -1)
-                    13      => -point.x - point.y - point.w, // (-1, -1,  0, -1)
-                    14 | 30 =>  point.x + point.z + point.w, // ( 1,  0,  1,  1)
-                    15      => -point.x + point.z + point.w, // (-1,  0,  1,  1)
-                    16      =>  point.x - point.z + point.w, // ( 1,  0, -1,  1)
-                    17      =>  point.x + point.z - point.w, // ( 1,  0,  1, -1)
-                    18      =>  point.x + point.z - point.w, // (-1,  0,  1, -1)
-                    19      =>  point.x + point.z - point.w, // ( 1,  0, -1, -1)
-                    20      => -point.x - point.z - point.w, // (-1,  0, -1, -1)
-                    21 | 31 =>  point.y + point.z + point.w, // ( 0,  1,  1,  1)
-                    22      => -point.y + point.z + point.w, // ( 0, -1,  1,  1)
-                    23      =>  point.y - point.z + point.w, // ( 0,  1, -1,  1)
-                    24      =>  point.y - point.z - point.w, // ( 0,  1,  1, -1)
-                    25      => -point.y - point.z - point.w, // ( 0, -1,  1, -1)
-                    26      =>  point.y - point.z - point.w, // ( 0,  1, -1, -1)
-                    27      => -point.y - point.z - point.w, // ( 0, -1, -1, -1)
-                    _ => unreachable!(),
-                }
+                let gradient = GRADIENTS_4D[hasher.hash(&(corner + offset).into_array()) & 0b11111];
+                gradient[0] * point.x
+                    + gradient[1] * point.y
+                    + gradient[2] * point.z
+                    + gradient[3] * point.w
             }
         }
     );
@@ -282,3 +314,30 @@ where
     // outliers and return it.
     result.clamp(-1.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+
+    #[test]
+    fn perlin_1d_is_smooth_across_zero() {
+        let hasher = PermutationTable::new(0);
+        let step = 1.0 / 1024.0;
+
+        for i in -4096..4096 {
+            let x = i as f64 * step;
+            let delta = (perlin_1d(x + step, &hasher) - perlin_1d(x, &hasher)).abs();
+            assert!(delta < 0.01, "discontinuity at {}", x);
+        }
+    }
+
+    #[test]
+    fn perlin_1d_lattice_points_are_zero() {
+        let hasher = PermutationTable::new(0);
+
+        for x in -8..8 {
+            assert_eq!(0.0, perlin_1d(x as f64, &hasher));
+        }
+    }
+}
@@ -33,6 +33,26 @@ where
     (F::one() - (F::one() / (n + F::one()).sqrt())) / n
 }
 
+/// Rotates a 3-dimensional point so that the main diagonal of the simplex
+/// lattice, `(1, 1, 1)`, points along the _z_ axis.
+///
+/// Sampling the rotated lattice on planes of constant _z_ hides the cubic
+/// structure of the skewed lattice, which otherwise shows up as axis-aligned
+/// streaks in 2-dimensional slices. The rotation is orthonormal, so distances
+/// and feature sizes are preserved.
+#[inline(always)]
+pub fn rotate_lattice_3d(point: Vector3<f64>) -> Vector3<f64> {
+    // -1/2 + 1/sqrt(12) and 1/sqrt(3)
+    const XY_FACTOR: f64 = -0.211_324_865_405_187;
+    const Z_FACTOR: f64 = 0.577_350_269_189_626;
+
+    let xy = point.x + point.y;
+    let s2 = xy * XY_FACTOR;
+    let zz = point.z * Z_FACTOR;
+
+    Vector3::new(point.x + s2 - zz, point.y + s2 - zz, xy * Z_FACTOR + zz)
+}
+
 /// The simplex noise code was adapted from code by Stefan Gustavson,
 /// http://staffwww.itn.liu.se/~stegu/aqsis/aqsis-newnoise/sdnoise1234.c
 ///
@@ -3,6 +3,19 @@ use crate::{
     permutationtable::NoiseHasher,
 };
 
+pub fn value_1d<NH>(point: f64, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let corner = point.floor() as isize;
+    let weight = (point - corner as f64).map_quintic();
+
+    let f0 = hasher.hash(&[corner]) as f64 / 255.0;
+    let f1 = hasher.hash(&[corner + 1]) as f64 / 255.0;
+
+    linear(f0, f1, weight) * 2.0 - 1.0
+}
+
 pub fn value_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
 where
     NH: NoiseHasher + ?Sized,
@@ -137,3 +150,32 @@ where
 
     result * 2.0 - 1.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+
+    #[test]
+    fn value_1d_lattice_points_return_hashed_values() {
+        let hasher = PermutationTable::new(0);
+
+        for x in -8..8 {
+            let expected = hasher.hash(&[x]) as f64 / 255.0 * 2.0 - 1.0;
+            assert_eq!(expected, value_1d(x as f64, &hasher));
+        }
+    }
+
+    #[test]
+    fn value_1d_is_smooth() {
+        let hasher = PermutationTable::new(0);
+        let step = 1.0 / 1024.0;
+
+        for i in -4096..4096 {
+            let x = i as f64 * step;
+            let delta = (value_1d(x + step, &hasher) - value_1d(x, &hasher)).abs();
+            // The quintic fade has a maximum slope of 1.875, applied over a range of 2.0.
+            assert!(delta <= 2.0 * 1.875 * step + 1e-9);
+        }
+    }
+}
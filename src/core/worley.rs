@@ -10,6 +10,25 @@ pub enum ReturnType {
     Value,
 }
 
+/// The feature point nearest to a sample point, as found by the Worley
+/// neighborhood search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NearestFeature<const DIM: usize> {
+    /// Lattice cell that owns the feature point.
+    pub cell: [isize; DIM],
+
+    /// Position of the feature point.
+    pub point: [f64; DIM],
+
+    /// Distance from the sample point to the feature point, as measured by the
+    /// distance function.
+    pub distance: f64,
+}
+
+/// Largest distance between a feature point and the integer corner of its
+/// cell, along any axis.
+const MAX_FEATURE_OFFSET: f64 = 0.5;
+
 pub mod distance_functions {
     pub fn euclidean(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
@@ -20,6 +39,13 @@ pub mod distance_functions {
             .sqrt()
     }
 
+    /// Squared Euclidean distance.
+    ///
+    /// This selects the same feature points as `euclidean`, but skips the
+    /// square root, which makes it considerably cheaper when distances are only
+    /// thresholded or compared. With `ReturnType::Distance`, the output is
+    /// `2 * d^2 - 1`, where `d` is the Euclidean distance in cell space, so it
+    /// is compressed towards -1.0 compared to the output for `euclidean`.
     pub fn euclidean_squared(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -45,12 +71,381 @@ pub mod distance_functions {
     }
 }
 
+/// Returns the `K` feature points nearest to `point`, in ascending order of
+/// distance.
+///
+/// Unlike the nearest-feature search, this visits the feature point of every
+/// cell within `search_radius` cells of the cell containing `point` along each
+/// axis, so the result is exact as long as the `K`th nearest feature point lies
+/// within that neighborhood. Entries are `None` if fewer than `K` cells were
+/// visited.
+pub(crate) fn k_nearest_features<F, NH, const DIM: usize, const K: usize>(
+    hasher: &NH,
+    distance_function: F,
+    point: [f64; DIM],
+    search_radius: usize,
+) -> [Option<NearestFeature<DIM>>; K]
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let mut nearest: [Option<NearestFeature<DIM>>; K] = [None; K];
+
+    visit_neighborhood(point, search_radius, |cell| {
+        let feature_point = feature_point(hasher, cell);
+        let candidate = NearestFeature {
+            cell,
+            point: feature_point,
+            distance: distance_function(&point, &feature_point),
+        };
+
+        // Insert the candidate into the sorted list, dropping the farthest.
+        if let Some(position) = nearest.iter().position(|entry| match entry {
+            Some(entry) => candidate.distance < entry.distance,
+            None => true,
+        }) {
+            nearest[position..].rotate_right(1);
+            nearest[position] = Some(candidate);
+        }
+    });
+
+    nearest
+}
+
+/// Returns a smooth minimum of the distances from `point` to the feature
+/// points of every cell within `search_radius` cells of the cell containing
+/// `point` along each axis.
+///
+/// The distances are blended with a log-sum-exp over the neighborhood, where
+/// `smoothing` sets the width of the blend in distance units. Wherever one
+/// feature point is much closer than the others the result matches its
+/// distance, while near cell borders the result is pulled below the nearest
+/// distance, which rounds off the crease between the cells. A smoothing of
+/// zero returns the nearest distance.
+pub(crate) fn smooth_nearest_distance<F, NH, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    point: [f64; DIM],
+    search_radius: usize,
+    smoothing: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let smoothing = smoothing.max(0.0);
+
+    // Track the nearest distance alongside the sum of the exponentials
+    // relative to it, which keeps the exponentials within range.
+    let mut nearest = f64::INFINITY;
+    let mut sum = 0.0;
+    visit_neighborhood(point, search_radius, |cell| {
+        let distance = distance_function(&point, &feature_point(hasher, cell));
+
+        if smoothing == 0.0 {
+            nearest = nearest.min(distance);
+        } else if distance < nearest {
+            sum = sum * (-(nearest - distance) / smoothing).exp() + 1.0;
+            nearest = distance;
+        } else {
+            sum += (-(distance - nearest) / smoothing).exp();
+        }
+    });
+
+    if smoothing == 0.0 {
+        return nearest;
+    }
+
+    nearest - smoothing * sum.ln()
+}
+
+/// Smallest weight of a cell when the distances are weighted. The weights
+/// are spread evenly between this and 1.0.
+pub const MIN_CELL_WEIGHT: f64 = 0.5;
+
+/// Returns the feature point nearest to `point` when the distance to each
+/// feature point is divided by the weight of its cell, see `cell_weight`.
+///
+/// Cells with a larger weight claim more space than their neighbours, which
+/// gives a multiplicatively weighted Voronoi diagram. The returned distance is
+/// the weighted distance multiplied by `MIN_CELL_WEIGHT`, which never exceeds
+/// the unweighted distance to the nearest feature point, so weighting keeps
+/// the range of the distance. Like `k_nearest_features`, this visits the feature
+/// point of every cell within `search_radius` cells of the cell containing
+/// `point` along each axis.
+pub(crate) fn weighted_nearest_feature<F, NH, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    point: [f64; DIM],
+    search_radius: usize,
+) -> NearestFeature<DIM>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let mut nearest: Option<NearestFeature<DIM>> = None;
+
+    visit_neighborhood(point, search_radius, |cell| {
+        let feature_point = feature_point(hasher, cell);
+        let distance =
+            distance_function(&point, &feature_point) * MIN_CELL_WEIGHT / cell_weight(hasher, cell);
+
+        let is_nearer = match nearest {
+            Some(nearest) => distance < nearest.distance,
+            None => true,
+        };
+
+        if is_nearer {
+            nearest = Some(NearestFeature {
+                cell,
+                point: feature_point,
+                distance,
+            });
+        }
+    });
+
+    // The neighborhood always contains at least the cell of `point`.
+    nearest.unwrap()
+}
+
+/// Returns the pseudo-random weight of `cell`, between `MIN_CELL_WEIGHT` and
+/// 1.0.
+///
+/// The weight is hashed from the coordinates of the cell rotated by one axis,
+/// with the last one offset, so it is independent of the position of the
+/// feature point and of the value of the cell. The rotated coordinates are
+/// still wrapped by a periodic hasher, so tiling is preserved.
+pub fn cell_weight<NH, const DIM: usize>(hasher: &NH, cell: [isize; DIM]) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let mut key = [0; DIM];
+    for (axis, key) in key.iter_mut().enumerate() {
+        *key = cell[(axis + 1) % DIM];
+    }
+    key[DIM - 1] += 1;
+
+    MIN_CELL_WEIGHT + (1.0 - MIN_CELL_WEIGHT) * hasher.hash(&key) as f64 / 255.0
+}
+
+/// Returns the output of Worley noise at `point` with the distances weighted
+/// by cell, see `weighted_nearest_feature`.
+pub(crate) fn weighted_worley<F, NH, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: [f64; DIM],
+    search_radius: usize,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let nearest = weighted_nearest_feature(hasher, distance_function, point, search_radius);
+
+    let value = match return_type {
+        ReturnType::Distance => nearest.distance,
+        ReturnType::Value => hasher.hash(&nearest.cell) as f64 / 255.0,
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Calls `visit` with every cell within `search_radius` cells of the cell
+/// containing `point` along each axis.
+fn visit_neighborhood<V, const DIM: usize>(point: [f64; DIM], search_radius: usize, mut visit: V)
+where
+    V: FnMut([isize; DIM]),
+{
+    let radius = search_radius as isize;
+    let mut base = [0; DIM];
+    for (base, value) in base.iter_mut().zip(point.iter()) {
+        *base = value.floor() as isize;
+    }
+
+    // Walk every offset in [-radius, radius] along each axis.
+    let mut offset = [-radius; DIM];
+    loop {
+        let mut cell = base;
+        for (cell, offset) in cell.iter_mut().zip(offset.iter()) {
+            *cell += offset;
+        }
+
+        visit(cell);
+
+        let mut axis = 0;
+        while axis < DIM && offset[axis] == radius {
+            offset[axis] = -radius;
+            axis += 1;
+        }
+        if axis == DIM {
+            break;
+        }
+        offset[axis] += 1;
+    }
+}
+
+/// Completes the corner search of `nearest_feature_2d`, `nearest_feature_3d`
+/// and `nearest_feature_4d`, which only visits the cells at `base` and one
+/// above it along each axis, where `base` is the cell containing `point`.
+///
+/// Each feature point lies within `MAX_FEATURE_OFFSET` of the integer corner
+/// of its cell along every axis, so only the cells from one below to two
+/// above `base` can hold a nearer feature point. A cell is only visited if the
+/// distance to the nearest point of the box its feature point lies in is
+/// below the nearest distance found so far, which rules out all of them for
+/// most points. This holds for any distance function that treats all axes
+/// alike and does not decrease as the difference along an axis grows, such as
+/// the `distance_functions`.
+fn complete_search<F, NH, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    point: [f64; DIM],
+    base: [isize; DIM],
+    mut nearest: NearestFeature<DIM>,
+) -> NearestFeature<DIM>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    // Returns the distance from `point` to a point `gap` away along one axis.
+    let axis_distance = |gap: f64| {
+        let mut other = point;
+        other[0] += gap;
+        distance_function(&point, &other)
+    };
+
+    // Find the gaps from `point` to the nearest feature points that the outer
+    // cells below and above `base` could hold along each axis, and return
+    // early if even the smallest of them is too far.
+    let mut gaps = [(0.0, 0.0); DIM];
+    for (gap, (value, base)) in gaps.iter_mut().zip(point.iter().zip(base.iter())) {
+        let frac = value - *base as f64;
+        *gap = (
+            frac + 1.0 - MAX_FEATURE_OFFSET,
+            2.0 - frac - MAX_FEATURE_OFFSET,
+        );
+    }
+    let smallest_gap = gaps
+        .iter()
+        .fold(f64::INFINITY, |acc, gap| acc.min(gap.0).min(gap.1));
+    if axis_distance(smallest_gap) >= nearest.distance {
+        return nearest;
+    }
+
+    // Find the outer cells along each axis whose feature points could still
+    // be nearer, as offsets from `base`.
+    let mut first = [0; DIM];
+    let mut last = [1; DIM];
+    let mut any_outer = false;
+    for (axis, gap) in gaps.iter().enumerate() {
+        if axis_distance(gap.0) < nearest.distance {
+            first[axis] = -1;
+            any_outer = true;
+        }
+        if axis_distance(gap.1) < nearest.distance {
+            last[axis] = 2;
+            any_outer = true;
+        }
+    }
+    if !any_outer {
+        return nearest;
+    }
+
+    // Walk the remaining offsets, skipping the inner cells that the corner
+    // search already covered and the cells whose feature point cannot beat the
+    // nearest one found so far.
+    let mut offset = first;
+    loop {
+        if offset.iter().any(|offset| !(0..=1).contains(offset)) {
+            let mut cell = base;
+            let mut box_point = point;
+            for axis in 0..DIM {
+                cell[axis] += offset[axis];
+                let corner = cell[axis] as f64;
+                box_point[axis] =
+                    point[axis].clamp(corner - MAX_FEATURE_OFFSET, corner + MAX_FEATURE_OFFSET);
+            }
+
+            if distance_function(&point, &box_point) < nearest.distance {
+                let candidate = feature_point(hasher, cell);
+                let distance = distance_function(&point, &candidate);
+                if distance < nearest.distance {
+                    nearest = NearestFeature {
+                        cell,
+                        point: candidate,
+                        distance,
+                    };
+                }
+            }
+        }
+
+        let mut axis = 0;
+        while axis < DIM && offset[axis] == last[axis] {
+            offset[axis] = first[axis];
+            axis += 1;
+        }
+        if axis == DIM {
+            break;
+        }
+        offset[axis] += 1;
+    }
+
+    nearest
+}
+
+/// Returns the position of the feature point of `cell`.
+///
+/// The searches calling this are only reached through `WorleyFeatures`, which
+/// is implemented for 2, 3 and 4 dimensions.
+fn feature_point<NH, const DIM: usize>(hasher: &NH, cell: [isize; DIM]) -> [f64; DIM]
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let index = hasher.hash(&cell);
+    let mut result = [0.0; DIM];
+
+    match DIM {
+        2 => result.copy_from_slice(&get_vec2(index).into_array()),
+        3 => result.copy_from_slice(&get_vec3(index).into_array()),
+        4 => result.copy_from_slice(&get_vec4(index).into_array()),
+        _ => unreachable!("Worley noise is only implemented for 2, 3 and 4 dimensions"),
+    }
+
+    for (value, cell) in result.iter_mut().zip(cell.iter()) {
+        *value += *cell as f64;
+    }
+
+    result
+}
+
 pub fn worley_2d<F, NH>(
     hasher: &NH,
     distance_function: F,
     return_type: ReturnType,
     point: Vector2<f64>,
 ) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let nearest = nearest_feature_2d(hasher, distance_function, point);
+
+    let value = match return_type {
+        ReturnType::Distance => nearest.distance,
+        ReturnType::Value => hasher.hash(&nearest.cell) as f64 / 255.0,
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Returns the feature point nearest to `point`, along with its cell and
+/// distance.
+pub fn nearest_feature_2d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector2<f64>,
+) -> NearestFeature<2>
 where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
@@ -70,7 +465,7 @@ where
 
     let mut seed_cell = near;
     let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -85,6 +480,7 @@ where
                 if cur_distance < distance {
                     distance = cur_distance;
                     seed_cell = test_point;
+                    seed_point = offset;
                 }
             }
         }
@@ -102,12 +498,19 @@ where
         test_point![far.x, far.y];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+    let nearest = NearestFeature {
+        cell: seed_cell.into_array(),
+        point: seed_point.into_array(),
+        distance,
     };
 
-    value * 2.0 - 1.0
+    complete_search(
+        hasher,
+        &distance_function,
+        point.into_array(),
+        cell.into_array(),
+        nearest,
+    )
 }
 
 #[rustfmt::skip]
@@ -135,6 +538,28 @@ pub fn worley_3d<F, NH>(
     return_type: ReturnType,
     point: Vector3<f64>,
 ) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let nearest = nearest_feature_3d(hasher, distance_function, point);
+
+    let value = match return_type {
+        ReturnType::Distance => nearest.distance,
+        ReturnType::Value => hasher.hash(&nearest.cell) as f64 / 255.0,
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Returns the feature point nearest to `point`, along with its cell and
+/// distance.
+#[inline(always)]
+pub fn nearest_feature_3d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector3<f64>,
+) -> NearestFeature<3>
 where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
@@ -154,7 +579,7 @@ where
 
     let mut seed_cell = near;
     let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -169,6 +594,7 @@ where
                 if cur_distance < distance {
                     distance = cur_distance;
                     seed_cell = test_point;
+                    seed_point = offset;
                 }
             }
         }
@@ -198,12 +624,19 @@ where
         test_point![far.x, far.y, far.z];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+    let nearest = NearestFeature {
+        cell: seed_cell.into_array(),
+        point: seed_point.into_array(),
+        distance,
     };
 
-    value * 2.0 - 1.0
+    complete_search(
+        hasher,
+        &distance_function,
+        point.into_array(),
+        cell.into_array(),
+        nearest,
+    )
 }
 
 #[rustfmt::skip]
@@ -243,6 +676,29 @@ pub fn worley_4d<F, NH>(
     return_type: ReturnType,
     point: Vector4<f64>,
 ) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let nearest = nearest_feature_4d(hasher, distance_function, point);
+
+    let value = match return_type {
+        ReturnType::Distance => nearest.distance,
+        ReturnType::Value => hasher.hash(&nearest.cell) as f64 / 255.0,
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Returns the feature point nearest to `point`, along with its cell and
+/// distance.
+#[inline(always)]
+#[allow(clippy::cognitive_complexity)]
+pub fn nearest_feature_4d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector4<f64>,
+) -> NearestFeature<4>
 where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
@@ -262,7 +718,7 @@ where
 
     let mut seed_cell = near;
     let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -277,6 +733,7 @@ where
                 if cur_distance < distance {
                     distance = cur_distance;
                     seed_cell = test_point;
+                    seed_point = offset;
                 }
             }
         }
@@ -331,12 +788,19 @@ where
         test_point![far.x, far.y, far.z, far.w];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+    let nearest = NearestFeature {
+        cell: seed_cell.into_array(),
+        point: seed_point.into_array(),
+        distance,
     };
 
-    value * 2.0 - 1.0
+    complete_search(
+        hasher,
+        &distance_function,
+        point.into_array(),
+        cell.into_array(),
+        nearest,
+    )
 }
 
 #[rustfmt::skip]
@@ -0,0 +1,41 @@
+use core::fmt;
+
+/// Errors returned when a noise function or utility is configured with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoiseError {
+    /// The lower bound of a range was not strictly below the upper bound, or
+    /// one of the bounds was not finite.
+    InvalidBounds { min: f64, max: f64 },
+
+    /// A map passed in to be filled did not have the size that was
+    /// configured.
+    SizeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+
+    /// A baked blob was truncated, damaged, or not written by this crate.
+    InvalidBlob,
+}
+
+impl fmt::Display for NoiseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NoiseError::InvalidBounds { min, max } => write!(
+                f,
+                "invalid bounds: expected finite min < max, found ({}, {})",
+                min, max
+            ),
+            NoiseError::SizeMismatch { expected, found } => write!(
+                f,
+                "size mismatch: expected a {}x{} map, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            NoiseError::InvalidBlob => write!(f, "invalid baked noise blob"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoiseError {}
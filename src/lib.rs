@@ -16,10 +16,12 @@
 #[macro_use]
 extern crate alloc;
 
+pub use crate::error::*;
 pub use crate::math::vectors::*;
 pub use crate::noise_fns::*;
 
 pub mod core;
+mod error;
 mod gradient;
 pub mod math;
 mod noise_fns;
@@ -16,3 +16,9 @@ pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
 pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
     (value.abs() * n) + -1.0_f64
 }
+
+/// Raises the magnitude of `value` to `exponent` while preserving its sign.
+#[inline]
+pub(crate) fn signed_pow(value: f64, exponent: f64) -> f64 {
+    value.signum() * value.abs().powf(exponent)
+}
@@ -1,12 +1,17 @@
+#[cfg(target_has_atomic = "64")]
+pub use self::probe::*;
 pub use self::{
-    cache::*, combiners::*, generators::*, modifiers::*, selectors::*, transformers::*,
+    cache::*, combiners::*, generators::*, graph::*, modifiers::*, selectors::*, transformers::*,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, sync::Arc};
 
 mod cache;
 mod combiners;
 mod generators;
+mod graph;
 mod modifiers;
+#[cfg(target_has_atomic = "64")]
+mod probe;
 mod selectors;
 mod transformers;
 
@@ -25,6 +30,80 @@ mod transformers;
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T, const DIM: usize> {
     fn get(&self, point: [T; DIM]) -> f64;
+
+    /// Returns the theoretical `(min, max)` range of the output values, if it
+    /// is known without sampling the function.
+    ///
+    /// Generators return their documented range, and combinators and modifiers
+    /// derive their range from those of their sources. Functions whose range
+    /// cannot be determined return `None`, which is the default.
+    fn output_range(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Returns a rough estimate of the cost of a single call to `get`, in units
+    /// of one evaluation of a basic generator.
+    ///
+    /// Generators cost 1, fractals cost the sum of their octaves, combinators
+    /// and selectors cost the sum of their sources, and wrappers that sample
+    /// their source several times, such as `Turbulence` or `Laplacian`, scale
+    /// the cost of their source accordingly. Summed over a whole graph, this
+    /// gives an order-of-magnitude estimate that can be used to size thread
+    /// pools or warn about expensive graphs before building a large map.
+    fn sample_cost(&self) -> u32 {
+        1
+    }
+
+    /// Returns the frequency of the most prominent features in the output,
+    /// in cycles per unit of input space, if it is known.
+    ///
+    /// Lattice generators such as `Perlin` and `Value` return 1.0, `Worley`
+    /// and the fractals return their (base) frequency, `ScalePoint`
+    /// multiplies the frequency of its source by its largest scale factor, and
+    /// combinators return the highest frequency among their sources. Functions
+    /// without a natural frequency, such as `Constant`, return `None`, which
+    /// is the default. This can be used to derive sensible step sizes for
+    /// derivative estimates or sensible bounds for map builders.
+    fn dominant_frequency(&self) -> Option<f64> {
+        None
+    }
+
+    /// Returns the highest frequency with significant content in the output,
+    /// in cycles per unit of input space, if it is known.
+    ///
+    /// This matches `dominant_frequency`, except that the fractals return the
+    /// frequency of their last octave rather than their base frequency, and
+    /// combinators return the highest frequency among their sources.
+    fn highest_frequency(&self) -> Option<f64> {
+        self.dominant_frequency()
+    }
+
+    /// Returns the largest spacing between samples that still resolves the
+    /// highest frequency in the output, as given by `highest_frequency`.
+    ///
+    /// By the Nyquist criterion, every cycle needs at least two samples, so the
+    /// spacing is `0.5 / highest_frequency`. Sampling more coarsely aliases the
+    /// finest octaves of a fractal into noisy speckle. Functions without a
+    /// known frequency return infinity.
+    fn recommended_sample_spacing(&self) -> f64 {
+        self.highest_frequency()
+            .map_or(f64::INFINITY, |frequency| 0.5 / frequency)
+    }
+
+    /// Returns a description of the structure of the noise function, for
+    /// logging and debugging composed graphs.
+    ///
+    /// Generators, combinators, selectors and the common modifiers describe
+    /// themselves as their type name followed by their key parameters in
+    /// braces and the descriptions of their sources in parentheses, such as
+    /// `Add(Turbulence{power:1}(Fbm{octaves:6, frequency:1}), Constant(0.5))`.
+    /// Other noise functions default to their bare type name.
+    fn describe(&self) -> String {
+        let name = core::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+
+        String::from(name.rsplit("::").next().unwrap_or(name))
+    }
 }
 
 impl<'a, T, M, const DIM: usize> NoiseFn<T, DIM> for &'a M
@@ -35,6 +114,31 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn output_range(&self) -> Option<(f64, f64)> {
+        M::output_range(*self)
+    }
+
+    #[inline]
+    fn sample_cost(&self) -> u32 {
+        M::sample_cost(*self)
+    }
+
+    #[inline]
+    fn dominant_frequency(&self) -> Option<f64> {
+        M::dominant_frequency(*self)
+    }
+
+    #[inline]
+    fn highest_frequency(&self) -> Option<f64> {
+        M::highest_frequency(*self)
+    }
+
+    #[inline]
+    fn describe(&self) -> String {
+        M::describe(*self)
+    }
 }
 
 impl<T, M, const DIM: usize> NoiseFn<T, DIM> for Box<M>
@@ -45,6 +149,129 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         M::get(self, point)
     }
+
+    #[inline]
+    fn output_range(&self) -> Option<(f64, f64)> {
+        M::output_range(self)
+    }
+
+    #[inline]
+    fn sample_cost(&self) -> u32 {
+        M::sample_cost(self)
+    }
+
+    #[inline]
+    fn dominant_frequency(&self) -> Option<f64> {
+        M::dominant_frequency(self)
+    }
+
+    #[inline]
+    fn highest_frequency(&self) -> Option<f64> {
+        M::highest_frequency(self)
+    }
+
+    #[inline]
+    fn describe(&self) -> String {
+        M::describe(self)
+    }
+}
+
+impl<T, M, const DIM: usize> NoiseFn<T, DIM> for Arc<M>
+where
+    M: NoiseFn<T, DIM> + ?Sized,
+{
+    #[inline]
+    fn get(&self, point: [T; DIM]) -> f64 {
+        M::get(self, point)
+    }
+
+    #[inline]
+    fn output_range(&self) -> Option<(f64, f64)> {
+        M::output_range(self)
+    }
+
+    #[inline]
+    fn sample_cost(&self) -> u32 {
+        M::sample_cost(self)
+    }
+
+    #[inline]
+    fn dominant_frequency(&self) -> Option<f64> {
+        M::dominant_frequency(self)
+    }
+
+    #[inline]
+    fn highest_frequency(&self) -> Option<f64> {
+        M::highest_frequency(self)
+    }
+
+    #[inline]
+    fn describe(&self) -> String {
+        M::describe(self)
+    }
+}
+
+/// Noise functions that can be cloned behind a trait object.
+///
+/// `Clone` is not object-safe, so a `Box<dyn NoiseFn<T, DIM>>` cannot be
+/// duplicated. Boxing a noise function as a `Box<dyn CloneNoiseFn<T, DIM>>`
+/// instead keeps it cloneable, which allows nodes of graphs assembled at
+/// runtime to be copied. This trait is implemented for every noise function
+/// that implements `Clone`.
+pub trait CloneNoiseFn<T, const DIM: usize>: NoiseFn<T, DIM> {
+    /// Returns a boxed copy of the noise function.
+    fn clone_box(&self) -> Box<dyn CloneNoiseFn<T, DIM>>;
+}
+
+impl<T, N, const DIM: usize> CloneNoiseFn<T, DIM> for N
+where
+    N: NoiseFn<T, DIM> + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CloneNoiseFn<T, DIM>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T, const DIM: usize> Clone for Box<dyn CloneNoiseFn<T, DIM>> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+/// Returns the higher of two optional frequencies, ignoring unknown ones.
+pub(crate) fn max_frequency(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Reseeds the two sources of a combinator with the first two seeds derived
+/// from `master`.
+pub(crate) fn reseed_pair<Source1, Source2>(
+    master: u32,
+    source1: Source1,
+    source2: Source2,
+) -> (Source1, Source2)
+where
+    Source1: Seedable,
+    Source2: Seedable,
+{
+    (
+        source1.set_seed(crate::permutationtable::derive_seed(master, 0)),
+        source2.set_seed(crate::permutationtable::derive_seed(master, 1)),
+    )
+}
+
+/// Replaces `value` with `replacement` if it is NaN or infinite and a
+/// replacement is set.
+#[inline]
+pub(crate) fn guard_non_finite(value: f64, replacement: Option<f64>) -> f64 {
+    match replacement {
+        Some(replacement) if !value.is_finite() => replacement,
+        _ => value,
+    }
 }
 
 /// Trait for functions that require a seed before generating their values
@@ -54,4 +281,190 @@ pub trait Seedable {
 
     /// Getter to retrieve the seed from the function
     fn seed(&self) -> u32;
+
+    /// Set the seed for the function from a name, such as the name of a world.
+    ///
+    /// The name is hashed into a `u32` seed with
+    /// [`seed_from_str`](crate::permutationtable::seed_from_str), which is
+    /// stable across platforms and versions.
+    fn set_seed_str(self, name: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_seed(crate::permutationtable::seed_from_str(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn boxed_sources_compose_in_combinators() {
+        let perlin = Perlin::new(1);
+        let value = Value::new(2);
+
+        let sources: Vec<Box<dyn NoiseFn<f64, 2>>> = vec![Box::new(perlin), Box::new(value)];
+        let mut sources = sources.into_iter();
+        let boxed = Add::new(sources.next().unwrap(), sources.next().unwrap());
+
+        let shared: Arc<dyn NoiseFn<f64, 2>> = Arc::new(perlin);
+        let arced = Add::new(shared.clone(), &shared);
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.31, i as f64 * 0.17];
+            assert_eq!(perlin.get(point) + value.get(point), boxed.get(point));
+            assert_eq!(perlin.get(point) * 2.0, arced.get(point));
+        }
+    }
+
+    #[test]
+    fn output_range_propagates_through_graph() {
+        let clamp = Clamp::new(Perlin::new(0)).set_bounds(-0.5, 0.25);
+        let graph = Add::new(clamp.clone(), Constant::new(1.0));
+
+        assert_eq!(Some((0.5, 1.25)), NoiseFn::<f64, 2>::output_range(&graph));
+
+        let abs = Abs::new(ScaleBias::new(Perlin::new(0)).set_bias(0.5));
+        assert_eq!(Some((0.0, 1.5)), NoiseFn::<f64, 3>::output_range(&abs));
+
+        let boxed: Box<dyn NoiseFn<f64, 2>> = Box::new(Negate::new(graph));
+        assert_eq!(Some((-1.25, -0.5)), boxed.output_range());
+
+        let unknown = Power::new(Perlin::new(0), Perlin::new(1));
+        assert_eq!(
+            None,
+            NoiseFn::<f64, 2>::output_range(&Add::new(unknown, clamp))
+        );
+    }
+
+    #[test]
+    fn sample_cost_propagates_through_graph() {
+        let fbm = Fbm::<Perlin>::new(0).set_octaves(4);
+        assert_eq!(4, NoiseFn::<f64, 2>::sample_cost(&fbm));
+
+        // The source plus one three-octave displacement per axis.
+        let turbulence = Turbulence::<_, Perlin>::new(Perlin::new(1));
+        assert_eq!(7, NoiseFn::<f64, 2>::sample_cost(&turbulence));
+        assert_eq!(10, NoiseFn::<f64, 3>::sample_cost(&turbulence));
+
+        let graph = Select::new(ScaleBias::new(fbm), turbulence, Constant::new(0.0));
+        assert_eq!(12, NoiseFn::<f64, 2>::sample_cost(&graph));
+
+        let boxed: Box<dyn NoiseFn<f64, 2>> = Box::new(Laplacian::new(graph));
+        assert_eq!(60, boxed.sample_cost());
+    }
+
+    #[test]
+    fn dominant_frequency_propagates_through_graph() {
+        let fbm = Fbm::<Perlin>::new(0).set_frequency(2.5);
+        assert_eq!(Some(2.5), NoiseFn::<f64, 2>::dominant_frequency(&fbm));
+        assert_eq!(
+            Some(1.0),
+            NoiseFn::<f64, 2>::dominant_frequency(&Perlin::new(0))
+        );
+        assert_eq!(
+            None,
+            NoiseFn::<f64, 2>::dominant_frequency(&Constant::new(0.0))
+        );
+
+        let scaled = ScalePoint::new(Value::new(1)).set_scale(4.0);
+        assert_eq!(Some(4.0), NoiseFn::<f64, 3>::dominant_frequency(&scaled));
+
+        let graph = Select::new(
+            ScaleBias::new(fbm),
+            Worley::new(2).set_frequency(3.0),
+            Constant::new(0.0),
+        );
+        assert_eq!(Some(3.0), NoiseFn::<f64, 2>::dominant_frequency(&graph));
+
+        let boxed: Box<dyn NoiseFn<f64, 2>> = Box::new(Laplacian::new(Add::new(graph, scaled)));
+        assert_eq!(Some(4.0), boxed.dominant_frequency());
+    }
+
+    #[test]
+    fn describe_prints_graph_structure() {
+        let fbm = Fbm::<Perlin>::new(0).set_octaves(6);
+        let graph = Add::new(Turbulence::<_, Perlin>::new(fbm), Constant::new(0.5));
+
+        assert_eq!(
+            "Add(Turbulence{power:1}(Fbm{octaves:6, frequency:1}), Constant(0.5))",
+            NoiseFn::<f64, 2>::describe(&graph)
+        );
+
+        // Functions without their own description fall back to their type name.
+        let boxed: Box<dyn NoiseFn<f64, 2>> =
+            Box::new(Max::new(Laplacian::new(Perlin::new(3)), Value::new(4)));
+        assert_eq!("Max(Laplacian, Value{seed:4})", boxed.describe());
+    }
+
+    #[test]
+    fn guard_non_finite_keeps_output_finite() {
+        let nan = Constant::new(f64::NAN);
+        let perlin = Perlin::new(0);
+
+        let unguarded = Add::new(nan, perlin);
+        assert!(NoiseFn::<f64, 2>::get(&unguarded, [0.3, 0.7]).is_nan());
+
+        let graph = Max::new(
+            Multiply::new(nan, Constant::new(f64::INFINITY)).set_guard_non_finite(Some(0.5)),
+            Min::new(nan, perlin).set_guard_non_finite(Some(0.0)),
+        );
+        let graph = Blend::new(
+            Add::new(nan, perlin).set_guard_non_finite(Some(0.0)),
+            Select::new(nan, graph, nan).set_guard_non_finite(Some(0.5)),
+            nan,
+        )
+        .set_guard_non_finite(Some(0.25));
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311];
+            let value = graph.get(point);
+            assert!(value.is_finite());
+
+            // The blend control is replaced with 0.25, and the selected source
+            // outputs max(0.5 * 0.5, min(0.0, perlin)), which is 0.25.
+            let expected = crate::math::interpolate::linear(perlin.get(point), 0.25, 0.25);
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn boxed_functions_can_be_cloned() {
+        let boxed: Box<dyn CloneNoiseFn<f64, 2>> = Box::new(Perlin::new(9));
+        let cloned = boxed.clone();
+        let graph = Add::new(cloned.clone_box(), boxed.clone());
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.31, i as f64 * -0.17];
+            assert_eq!(boxed.get(point), cloned.get(point));
+            assert_eq!(boxed.get(point) * 2.0, graph.get(point));
+        }
+    }
+
+    #[test]
+    fn recommended_spacing_follows_highest_frequency() {
+        use crate::utils::{NoiseMapBuilder, PlaneMapBuilder};
+
+        let low = Fbm::<Perlin>::new(0).set_octaves(2);
+        let high = Fbm::<Perlin>::new(0).set_octaves(8);
+        let low_spacing = NoiseFn::<f64, 2>::recommended_sample_spacing(&low);
+        let high_spacing = NoiseFn::<f64, 2>::recommended_sample_spacing(&high);
+        assert!(high_spacing < low_spacing);
+
+        // The last octave of the fractal sets the spacing, even behind other
+        // functions.
+        let graph = Add::new(ScaleBias::new(high.clone()), Constant::new(0.5));
+        let expected = 0.5 / Fbm::<Perlin>::DEFAULT_LACUNARITY.powi(7);
+        assert!((NoiseFn::<f64, 2>::recommended_sample_spacing(&graph) - expected).abs() < 1e-12);
+        assert_eq!(
+            f64::INFINITY,
+            NoiseFn::<f64, 2>::recommended_sample_spacing(&Constant::new(0.0))
+        );
+
+        let builder = PlaneMapBuilder::<_, 2>::new(high).set_size(64, 64);
+        assert!(builder.is_undersampled());
+        assert!(!builder.set_size(4096, 4096).is_undersampled());
+    }
 }
@@ -54,6 +54,22 @@ where
             }
         }
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
 }
 
 fn quick_eq(a: &[f64], b: &[f64]) -> bool {
@@ -1,7 +1,14 @@
-pub use self::{add::*, max::*, min::*, multiply::*, power::*};
+pub use self::{
+    add::*, average::*, ensemble::*, max::*, max_detail::*, min::*, multiply::*, power::*,
+    saturating_add::*,
+};
 
 mod add;
+mod average;
+mod ensemble;
 mod max;
+mod max_detail;
 mod min;
 mod multiply;
 mod power;
+mod saturating_add;
@@ -0,0 +1,127 @@
+use crate::noise_fns::{max_frequency, NoiseFn};
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+
+/// Noise function that outputs the arithmetic mean of the output values from
+/// any number of source functions.
+///
+/// Unlike chaining [`Add`](crate::Add), the result stays within the range of
+/// the source outputs, so averaging sources in the `[-1, 1]` range produces a
+/// value in the `[-1, 1]` range. Averaging an empty set of sources outputs 0.0.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Average<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs the values to be averaged.
+    pub sources: Vec<Source>,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Average<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(sources: Vec<Source>) -> Self {
+        Self {
+            sources,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Average<T, Source, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        if self.sources.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = self.sources.iter().map(|source| source.get(point)).sum();
+
+        sum / self.sources.len() as f64
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        if self.sources.is_empty() {
+            return Some((0.0, 0.0));
+        }
+
+        let mut min = 0.0;
+        let mut max = 0.0;
+        for source in &self.sources {
+            let (source_min, source_max) = source.output_range()?;
+            min += source_min;
+            max += source_max;
+        }
+
+        let count = self.sources.len() as f64;
+        Some((min / count, max / count))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.sources
+            .iter()
+            .map(|source| source.dominant_frequency())
+            .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.sources
+            .iter()
+            .map(|source| source.highest_frequency())
+            .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        let sources: Vec<String> = self
+            .sources
+            .iter()
+            .map(|source| source.describe())
+            .collect();
+
+        format!("Average({})", sources.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Seedable};
+    use alloc::boxed::Box;
+
+    #[test]
+    fn average_of_copies_is_identity() {
+        let perlin = Perlin::new(7);
+        let average = Average::new(vec![perlin; 5]);
+
+        for point in [[0.3, 1.7], [-4.2, 2.5], [10.1, -0.9]] {
+            let expected = perlin.get(point);
+            assert!((average.get(point) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn average_stays_bounded() {
+        let sources: Vec<Box<dyn NoiseFn<f64, 2>>> = (0..4)
+            .map(|seed| Box::new(Perlin::default().set_seed(seed)) as Box<dyn NoiseFn<f64, 2>>)
+            .collect();
+        let average = Average::new(sources);
+
+        for i in 0..100 {
+            let value = average.get([i as f64 * 0.37, i as f64 * -0.21]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    noise_fns::{Average, NoiseFn, Seedable},
+    permutationtable::derive_seed,
+};
+use alloc::{format, string::String};
+
+/// Noise function that outputs the mean of several independently seeded copies
+/// of the same generator.
+///
+/// Averaging independent realizations smooths out the quirks of any single
+/// seed, and the output approaches a Gaussian field as the number of copies
+/// grows. Unlike [`Fbm`](crate::Fbm), which varies the frequency between its
+/// octaves, every copy is sampled at the same frequency. The variance of the
+/// output drops roughly in proportion to the number of copies, so the output
+/// is flatter than that of the base generator.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Ensemble<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Averages the reseeded copies of the base generator.
+    pub average: Average<T, Source, DIM>,
+}
+
+impl<T, Source, const DIM: usize> Ensemble<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Clone + Seedable,
+{
+    /// Creates an ensemble of `count` copies of `base`, seeded with
+    /// [`derive_seed`] from the seed of `base`.
+    ///
+    /// Deriving the seeds keeps the copies independent of the octaves of a
+    /// fractal with the same seed, which use consecutive seeds, and of the
+    /// ensembles built from neighbouring seeds.
+    pub fn new(base: Source, count: usize) -> Self {
+        let seed = base.seed();
+        let sources = (0..count)
+            .map(|index| base.clone().set_seed(derive_seed(seed, index as u32)))
+            .collect();
+
+        Self {
+            average: Average::new(sources),
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Ensemble<T, Source, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.average.get(point)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.average.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.average.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.average.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.average.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!("Ensemble({})", self.average.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+    use alloc::vec::Vec;
+
+    fn variance<F: NoiseFn<f64, 2>>(function: &F) -> f64 {
+        let samples: Vec<f64> = (0..4000)
+            .map(|i| function.get([i as f64 * 0.137, i as f64 * 0.071 + 0.5]))
+            .collect();
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        samples.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / samples.len() as f64
+    }
+
+    #[test]
+    fn ensemble_reduces_variance() {
+        let single = Perlin::new(4);
+        let ensemble = Ensemble::<_, _, 2>::new(single, 8);
+        let sources = &ensemble.average.sources;
+
+        assert_eq!(8, sources.len());
+        assert_eq!(derive_seed(4, 0), sources[0].seed());
+        assert_eq!(derive_seed(4, 7), sources[7].seed());
+
+        // Ensembles of neighbouring seeds share no copies.
+        let neighbour = Ensemble::<_, _, 2>::new(Perlin::new(5), 8);
+        for source in &neighbour.average.sources {
+            assert!(sources.iter().all(|other| other.seed() != source.seed()));
+        }
+
+        assert!(variance(&ensemble) < variance(&single) * 0.5);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::noise_fns::{max_frequency, reseed_pair, NoiseFn, Seedable};
+use alloc::{format, string::String};
+
+/// Noise function that outputs the value of whichever of two source functions
+/// has the steeper slope at the input value.
+///
+/// The slope of each source function is estimated with central differences at
+/// `±epsilon` along each axis, and the output value is taken from the source
+/// with the larger gradient magnitude, preferring the first source when they
+/// are equal. This keeps the sharpest features of either layer, such as a
+/// ridge from one layer cutting through flat ground from the other.
+///
+/// Switching between the sources is not smoothed, so the output can jump where
+/// the steeper source changes.
+///
+/// As with [`Laplacian`](crate::Laplacian), the step should suit the frequency
+/// of the sources, which
+/// [`set_epsilon_for_frequency`](Self::set_epsilon_for_frequency) and
+/// [`set_epsilon_from_sources`](Self::set_epsilon_from_sources) take care of.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct MaxDetail<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Distance from the input value at which the slopes are sampled. The
+    /// default is set to 0.1.
+    pub epsilon: f64,
+}
+
+impl<Source1, Source2> MaxDetail<Source1, Source2> {
+    pub const DEFAULT_EPSILON: f64 = 0.1;
+
+    /// Fraction of the sources' wavelength used as the step by
+    /// [`set_epsilon_for_frequency`](Self::set_epsilon_for_frequency).
+    pub const EPSILON_PER_WAVELENGTH: f64 = 0.1;
+
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            epsilon: Self::DEFAULT_EPSILON,
+        }
+    }
+
+    /// Creates the combinator with its sources reseeded from `master`, see
+    /// [`derive_seed`](crate::permutationtable::derive_seed).
+    pub fn from_seed(master: u32, source1: Source1, source2: Source2) -> Self
+    where
+        Source1: Seedable,
+        Source2: Seedable,
+    {
+        let (source1, source2) = reseed_pair(master, source1, source2);
+
+        Self::new(source1, source2)
+    }
+
+    pub fn set_epsilon(self, epsilon: f64) -> Self {
+        Self { epsilon, ..self }
+    }
+
+    /// Sets the step to a fixed fraction of the wavelength of sources with the
+    /// given frequency.
+    pub fn set_epsilon_for_frequency(self, frequency: f64) -> Self {
+        self.set_epsilon(Self::EPSILON_PER_WAVELENGTH / frequency)
+    }
+
+    /// Sets the step from the higher of the
+    /// [`dominant_frequency`](NoiseFn::dominant_frequency) of the two sources,
+    /// so that the finer source is still resolved. The step is left unchanged
+    /// if neither frequency is known and positive.
+    pub fn set_epsilon_from_sources<const DIM: usize>(self) -> Self
+    where
+        Source1: NoiseFn<f64, DIM>,
+        Source2: NoiseFn<f64, DIM>,
+    {
+        let frequency = max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        );
+
+        match frequency {
+            Some(frequency) if frequency > 0.0 => self.set_epsilon_for_frequency(frequency),
+            _ => self,
+        }
+    }
+}
+
+/// Returns the squared magnitude of the gradient of `source` at `point`,
+/// scaled by `(2 * epsilon)^2`.
+fn scaled_gradient_squared<Source, const DIM: usize>(
+    source: &Source,
+    point: [f64; DIM],
+    epsilon: f64,
+) -> f64
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    let mut sum = 0.0;
+    for axis in 0..DIM {
+        let mut forward = point;
+        let mut backward = point;
+        forward[axis] += epsilon;
+        backward[axis] -= epsilon;
+
+        let difference = source.get(forward) - source.get(backward);
+        sum += difference * difference;
+    }
+
+    sum
+}
+
+impl<Source1, Source2, const DIM: usize> NoiseFn<f64, DIM> for MaxDetail<Source1, Source2>
+where
+    Source1: NoiseFn<f64, DIM>,
+    Source2: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let slope1 = scaled_gradient_squared(&self.source1, point, self.epsilon);
+        let slope2 = scaled_gradient_squared(&self.source2, point, self.epsilon);
+
+        if slope2 > slope1 {
+            self.source2.get(point)
+        } else {
+            self.source1.get(point)
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+
+        Some((min1.min(min2), max1.max(max2)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        // Two samples along each axis of both sources, plus the chosen value.
+        let cost1 = self.source1.sample_cost().saturating_mul(2 * DIM as u32);
+        let cost2 = self.source2.sample_cost().saturating_mul(2 * DIM as u32);
+
+        cost1
+            .saturating_add(cost2)
+            .saturating_add(self.source1.sample_cost().max(self.source2.sample_cost()))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "MaxDetail({}, {})",
+            self.source1.describe(),
+            self.source2.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Fbm, MultiFractal, Perlin};
+
+    /// Ridge along the _y_ axis, flat beyond half a unit from it.
+    struct Ridge;
+
+    impl NoiseFn<f64, 2> for Ridge {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            (1.0 - point[0].abs() * 2.0).max(0.0)
+        }
+    }
+
+    #[test]
+    fn ridge_wins_over_flat_source() {
+        let detail = MaxDetail::new(Constant::new(0.25), Ridge).set_epsilon(0.01);
+
+        for i in 0..20 {
+            let y = i as f64 * 0.37 - 3.0;
+
+            // On the flanks of the ridge, the ridge is steeper.
+            for &x in &[-0.4, -0.2, 0.15, 0.3, 0.45] {
+                assert_eq!(Ridge.get([x, y]), detail.get([x, y]));
+            }
+
+            // Away from the ridge, both are flat and the first source is kept.
+            for &x in &[-2.0, -0.7, 0.6, 1.5] {
+                assert_eq!(0.25, detail.get([x, y]));
+            }
+        }
+    }
+
+    #[test]
+    fn epsilon_follows_finer_source() {
+        let detail = MaxDetail::new(
+            Fbm::<Perlin>::new(0).set_frequency(2.0),
+            Fbm::<Perlin>::new(1).set_frequency(5.0),
+        )
+        .set_epsilon_from_sources::<2>();
+        assert!((detail.epsilon - 0.02).abs() < 1e-12);
+
+        // Without a known frequency, the step is kept.
+        let detail = MaxDetail::new(Constant::new(0.25), Ridge).set_epsilon_from_sources::<2>();
+        assert_eq!(
+            MaxDetail::<Constant, Ridge>::DEFAULT_EPSILON,
+            detail.epsilon
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{guard_non_finite, max_frequency, reseed_pair, NoiseFn, Seedable};
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that outputs the smaller of the two output values from two source
@@ -15,6 +16,11 @@ where
     /// Outputs a value.
     pub source2: Source2,
 
+    /// Value that replaces NaN or infinite outputs from the source functions,
+    /// or `None` to pass them through unchanged. Default is `None`.
+    #[serde(default)]
+    pub guard_non_finite: Option<f64>,
+
     phantom: PhantomData<T>,
 }
 
@@ -27,9 +33,32 @@ where
         Self {
             source1,
             source2,
+            guard_non_finite: None,
             phantom: PhantomData,
         }
     }
+
+    /// Creates the combinator with its sources reseeded from `master`, see
+    /// [`derive_seed`](crate::permutationtable::derive_seed).
+    pub fn from_seed(master: u32, source1: Source1, source2: Source2) -> Self
+    where
+        Source1: Seedable,
+        Source2: Seedable,
+    {
+        let (source1, source2) = reseed_pair(master, source1, source2);
+
+        Self::new(source1, source2)
+    }
+
+    /// Replaces NaN or infinite outputs from the source functions with the
+    /// given value before they are combined, so that a misbehaving source does
+    /// not poison the rest of the graph. `None` disables the guard.
+    pub fn set_guard_non_finite(self, replacement: Option<f64>) -> Self {
+        Self {
+            guard_non_finite: replacement,
+            ..self
+        }
+    }
 }
 
 impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Min<T, Source1, Source2, DIM>
@@ -39,6 +68,44 @@ where
     Source2: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source1.get(point)).min(self.source2.get(point))
+        let value1 = guard_non_finite(self.source1.get(point), self.guard_non_finite);
+        let value2 = guard_non_finite(self.source2.get(point), self.guard_non_finite);
+
+        value1.min(value2)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+
+        Some((min1.min(min2), max1.min(max2)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Min({}, {})",
+            self.source1.describe(),
+            self.source2.describe()
+        )
     }
 }
@@ -1,4 +1,5 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{max_frequency, reseed_pair, NoiseFn, Seedable};
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that raises the output value from the first source function
@@ -30,6 +31,18 @@ where
             phantom: PhantomData,
         }
     }
+
+    /// Creates the combinator with its sources reseeded from `master`, see
+    /// [`derive_seed`](crate::permutationtable::derive_seed).
+    pub fn from_seed(master: u32, source1: Source1, source2: Source2) -> Self
+    where
+        Source1: Seedable,
+        Source2: Seedable,
+    {
+        let (source1, source2) = reseed_pair(master, source1, source2);
+
+        Self::new(source1, source2)
+    }
 }
 
 impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Power<T, Source1, Source2, DIM>
@@ -41,4 +54,32 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         (self.source1.get(point)).powf(self.source2.get(point))
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Power({}, {})",
+            self.source1.describe(),
+            self.source2.describe()
+        )
+    }
 }
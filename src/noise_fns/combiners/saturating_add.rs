@@ -0,0 +1,149 @@
+use crate::noise_fns::{max_frequency, reseed_pair, NoiseFn, Seedable};
+use alloc::{format, string::String};
+use core::marker::PhantomData;
+
+/// Noise function that adds the output values from two source functions
+/// without leaving the `[-1, 1]` range.
+///
+/// Values of the same sign are combined with the "screen" blend, `a + b - a*b`
+/// for positive values and `a + b + a*b` for negative values, so that the sum
+/// approaches but never exceeds ±1. Values of opposite sign are simply added,
+/// since their sum already lies within range. This makes it possible to stack
+/// noise layers without clipping them afterwards.
+///
+/// The output values from the source functions are clamped to `[-1, 1]` before
+/// being combined.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SaturatingAdd<T, Source1, Source2, const DIM: usize>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source1, Source2, const DIM: usize> SaturatingAdd<T, Source1, Source2, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates the combinator with its sources reseeded from `master`, see
+    /// [`derive_seed`](crate::permutationtable::derive_seed).
+    pub fn from_seed(master: u32, source1: Source1, source2: Source2) -> Self
+    where
+        Source1: Seedable,
+        Source2: Seedable,
+    {
+        let (source1, source2) = reseed_pair(master, source1, source2);
+
+        Self::new(source1, source2)
+    }
+}
+
+/// Combines two values with a signed screen blend.
+#[inline]
+fn saturating_add(a: f64, b: f64) -> f64 {
+    let a = a.clamp(-1.0, 1.0);
+    let b = b.clamp(-1.0, 1.0);
+
+    if a >= 0.0 && b >= 0.0 {
+        a + b - a * b
+    } else if a <= 0.0 && b <= 0.0 {
+        a + b + a * b
+    } else {
+        a + b
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM>
+    for SaturatingAdd<T, Source1, Source2, DIM>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        saturating_add(self.source1.get(point), self.source2.get(point))
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+
+        // The blend never decreases when either input increases, so the bounds
+        // map directly onto the new bounds.
+        Some((saturating_add(min1, min2), saturating_add(max1, max2)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "SaturatingAdd({}, {})",
+            self.source1.describe(),
+            self.source2.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn output_never_exceeds_unit_range() {
+        let steps = 40;
+        for i in 0..=steps {
+            for j in 0..=steps {
+                let a = -1.5 + 3.0 * i as f64 / steps as f64;
+                let b = -1.5 + 3.0 * j as f64 / steps as f64;
+                let add = SaturatingAdd::new(Constant::new(a), Constant::new(b));
+                let value = add.get([0.0, 0.0]);
+
+                assert!((-1.0..=1.0).contains(&value), "{} + {} = {}", a, b, value);
+            }
+        }
+    }
+
+    #[test]
+    fn behaves_like_add_near_zero() {
+        let add = SaturatingAdd::new(Constant::new(0.01), Constant::new(0.02));
+        assert!((add.get([0.0, 0.0]) - 0.0298).abs() < 1e-12);
+
+        let add = SaturatingAdd::new(Constant::new(0.6), Constant::new(-0.4));
+        assert!((add.get([0.0, 0.0]) - 0.2).abs() < 1e-12);
+    }
+}
@@ -1,16 +1,56 @@
 pub use self::{
-    checkerboard::*, constant::*, cylinders::*, fractals::*, open_simplex::*, perlin::*,
-    perlin_surflet::*, simplex::*, super_simplex::*, value::*, worley::*,
+    cell_constant::*, checkerboard::*, constant::*, cylinders::*, fractals::*, hex_value::*,
+    open_simplex::*, perlin::*, perlin_surflet::*, simplex::*, sine::*, sparse::*,
+    super_simplex::*, value::*, worley::*,
 };
 
+mod cell_constant;
 mod checkerboard;
 mod constant;
 mod cylinders;
 mod fractals;
+mod hex_value;
 mod open_simplex;
 mod perlin;
 mod perlin_surflet;
 mod simplex;
+mod sine;
+mod sparse;
 mod super_simplex;
 mod value;
 mod worley;
+
+/// Returns the default per-axis frequency, 1.0 on every axis.
+fn default_axis_frequency() -> [f64; 4] {
+    [1.0; 4]
+}
+
+/// Pads a per-axis frequency to four axes, with a frequency of 1.0 on the axes
+/// that are not given.
+fn pad_axis_frequency<const DIM: usize>(frequency: [f64; DIM]) -> [f64; 4] {
+    assert!(
+        DIM <= 4,
+        "per-axis frequencies support at most four dimensions"
+    );
+
+    let mut padded = default_axis_frequency();
+    padded[..DIM].copy_from_slice(&frequency);
+    padded
+}
+
+/// Multiplies each coordinate of the input value by the frequency of its axis.
+fn scale_axes<const DIM: usize>(mut point: [f64; DIM], frequency: &[f64; 4]) -> [f64; DIM] {
+    for (value, frequency) in point.iter_mut().zip(frequency) {
+        *value *= frequency;
+    }
+
+    point
+}
+
+/// Returns the largest per-axis frequency among the first `DIM` axes.
+fn max_axis_frequency<const DIM: usize>(frequency: &[f64; 4]) -> f64 {
+    frequency[..DIM]
+        .iter()
+        .map(|frequency| frequency.abs())
+        .fold(0.0, f64::max)
+}
@@ -0,0 +1,110 @@
+use crate::{
+    noise_fns::{NoiseFn, Seedable},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs a flat value for each cell of a grid.
+///
+/// The input space is divided into cubic cells of side `cell_size`, and every
+/// point within a cell outputs the same pseudo-random value within `[-1, 1]`,
+/// derived by hashing the integer coordinates of the cell. Cells that share a
+/// face always output different values.
+///
+/// This noise function is not very useful by itself, but it can be used for
+/// debugging purposes, such as rendering each chunk of a world in its own
+/// shade to make chunk boundaries visible.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CellConstant {
+    /// Length of the side of each cell. The default is 1.0.
+    pub cell_size: f64,
+
+    seed: u32,
+    perm_table: PermutationTable,
+}
+
+impl CellConstant {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_CELL_SIZE: f64 = 1.0;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            cell_size: Self::DEFAULT_CELL_SIZE,
+            seed,
+            perm_table: PermutationTable::new(seed),
+        }
+    }
+
+    pub fn set_cell_size(self, cell_size: f64) -> Self {
+        Self { cell_size, ..self }
+    }
+}
+
+impl Default for CellConstant {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for CellConstant {
+    fn set_seed(self, seed: u32) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl<const DIM: usize> NoiseFn<f64, DIM> for CellConstant {
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut cell = [0; DIM];
+        for (cell, &value) in cell.iter_mut().zip(&point) {
+            *cell = (value / self.cell_size).floor() as isize;
+        }
+
+        self.perm_table.hash(&cell) as f64 / 127.5 - 1.0
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_is_flat_within_cell() {
+        let cells = CellConstant::new(7).set_cell_size(16.0);
+        let value = cells.get([16.0, -32.0]);
+
+        for i in 0..16 {
+            let point = [16.0 + i as f64 * 0.99, -32.0 + i as f64 * 0.5];
+            assert_eq!(value, cells.get(point));
+        }
+    }
+
+    #[test]
+    fn adjacent_cells_differ() {
+        let cells = CellConstant::new(7).set_cell_size(16.0);
+
+        for i in -10..10 {
+            let point = [i as f64 * 16.0 + 8.0, 8.0, -8.0];
+            let value = cells.get(point);
+            assert_ne!(value, cells.get([point[0] + 16.0, point[1], point[2]]));
+            assert_ne!(value, cells.get([point[0], point[1] + 16.0, point[2]]));
+            assert_ne!(value, cells.get([point[0], point[1], point[2] + 16.0]));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
 
 /// Noise function that outputs a constant value.
 ///
@@ -23,4 +24,12 @@ impl<T: Copy, const N: usize> NoiseFn<T, N> for Constant {
     fn get(&self, _point: [T; N]) -> f64 {
         self.value
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((self.value, self.value))
+    }
+
+    fn describe(&self) -> String {
+        format!("Constant({})", self.value)
+    }
 }
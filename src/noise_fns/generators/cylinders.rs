@@ -35,16 +35,28 @@ impl NoiseFn<f64, 2> for Cylinders {
     fn get(&self, point: [f64; 2]) -> f64 {
         spheres_2d(point.into(), self.frequency)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
 }
 
 impl NoiseFn<f64, 3> for Cylinders {
     fn get(&self, point: [f64; 3]) -> f64 {
         spheres_2d(Vector2::new(point[0], point[1]), self.frequency)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
 }
 
 impl NoiseFn<f64, 4> for Cylinders {
     fn get(&self, point: [f64; 4]) -> f64 {
         spheres_2d(Vector2::new(point[0], point[1]), self.frequency)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
 }
@@ -1,13 +1,14 @@
-pub use self::{basicmulti::*, billow::*, fbm::*, hybridmulti::*, ridgedmulti::*};
+pub use self::{basicmulti::*, billow::*, crackle::*, fbm::*, hybridmulti::*, ridgedmulti::*};
 use alloc::vec::Vec;
 
 mod basicmulti;
 mod billow;
+mod crackle;
 mod fbm;
 mod hybridmulti;
 mod ridgedmulti;
 
-use crate::Seedable;
+use crate::{math::scale_shift, Seedable};
 
 /// Trait for `MultiFractal` functions
 pub trait MultiFractal {
@@ -18,6 +19,70 @@ pub trait MultiFractal {
     fn set_lacunarity(self, lacunarity: f64) -> Self;
 
     fn set_persistence(self, persistence: f64) -> Self;
+
+    /// Sets a translation that is applied to each octave, scaled by the
+    /// octave's index, so that every octave samples a different, uncorrelated
+    /// region of its source function. The fractals in this crate take at most
+    /// four components and panic if given more.
+    ///
+    /// The default implementation ignores the offset, for fractals that do not
+    /// support it.
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = offset;
+        self
+    }
+}
+
+/// Transform applied to the output of each octave of an `Fbm` before the
+/// octaves are summed.
+///
+/// Each transform maps an octave in `[-1, 1]` back into `[-1, 1]`, so the
+/// scaling of the fractal is unaffected.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum OctaveTransform {
+    /// Octaves are summed unchanged, as in plain `Fbm`.
+    None,
+
+    /// Octaves are folded with `2|x| - 1`, the per-octave transform of
+    /// `Billow`.
+    Abs,
+
+    /// Octaves are folded into ridges with `2(1 - |x|)^2 - 1`, the
+    /// per-octave profile of `RidgedMulti`. Unlike `RidgedMulti`, each octave
+    /// is not weighted by the previous one.
+    Ridge,
+
+    /// Octaves are passed through a custom function. Fractals using a custom
+    /// transform cannot be serialized.
+    #[serde(skip)]
+    Custom(fn(f64) -> f64),
+}
+
+impl OctaveTransform {
+    #[inline]
+    pub fn apply(self, value: f64) -> f64 {
+        match self {
+            OctaveTransform::None => value,
+            OctaveTransform::Abs => scale_shift(value, 2.0),
+            OctaveTransform::Ridge => {
+                let ridge = 1.0 - value.abs();
+                ridge * ridge * 2.0 - 1.0
+            }
+            OctaveTransform::Custom(transform) => transform(value),
+        }
+    }
+}
+
+/// Returns the frequency of the last octave of a fractal.
+fn highest_octave_frequency(frequency: f64, lacunarity: f64, octaves: usize) -> f64 {
+    frequency * lacunarity.powi(octaves as i32 - 1)
+}
+
+fn default_octave_transform() -> OctaveTransform {
+    OctaveTransform::None
 }
 
 fn build_sources<Source>(seed: u32, octaves: usize) -> Vec<Source>
@@ -31,3 +96,23 @@ where
     }
     sources
 }
+
+fn pad_octave_offset<const DIM: usize>(offset: [f64; DIM]) -> [f64; 4] {
+    assert!(DIM <= 4, "octave offsets support at most four dimensions");
+
+    let mut padded = [0.0; 4];
+    padded[..DIM].copy_from_slice(&offset);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, Perlin};
+
+    #[test]
+    #[should_panic(expected = "at most four dimensions")]
+    fn octave_offset_rejects_extra_components() {
+        let _ = Fbm::<Perlin>::default().set_octave_offset([1.0; 5]);
+    }
+}
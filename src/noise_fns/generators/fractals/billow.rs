@@ -2,7 +2,7 @@ use crate::{
     math::{scale_shift, vectors::*},
     noise_fns::{MultiFractal, NoiseFn, Seedable},
 };
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 /// Noise function that outputs "billowy" noise.
 ///
@@ -41,6 +41,12 @@ pub struct Billow<T> {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
+    /// Translation applied to each successive octave, scaled by the octave's
+    /// index. Offsetting the octaves makes each one sample an uncorrelated
+    /// region of its source function. The default offset is zero.
+    #[serde(default)]
+    pub octave_offset: [f64; 4],
+
     seed: u32,
     sources: Vec<T>,
     scale_factor: f64,
@@ -64,6 +70,7 @@ where
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            octave_offset: [0.0; 4],
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
@@ -125,6 +132,13 @@ where
             ..self
         }
     }
+
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        Self {
+            octave_offset: super::pad_octave_offset(offset),
+            ..self
+        }
+    }
 }
 
 impl<T> Seedable for Billow<T>
@@ -155,6 +169,7 @@ where
 {
     fn get(&self, point: [f64; 2]) -> f64 {
         let mut point = Vector2::from(point);
+        let octave_offset = Vector2::new(self.octave_offset[0], self.octave_offset[1]);
 
         let mut result = 0.0;
 
@@ -162,7 +177,7 @@ where
 
         for x in 0..self.octaves {
             // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Take the abs of the signal, then scale and shift back to
             // the [-1,1] range.
@@ -181,6 +196,33 @@ where
         // Scale the result to the [-1,1] range.
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Billow{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 3-dimensional Billow noise
@@ -190,6 +232,11 @@ where
 {
     fn get(&self, point: [f64; 3]) -> f64 {
         let mut point = Vector3::from(point);
+        let octave_offset = Vector3::new(
+            self.octave_offset[0],
+            self.octave_offset[1],
+            self.octave_offset[2],
+        );
 
         let mut result = 0.0;
 
@@ -197,7 +244,7 @@ where
 
         for x in 0..self.octaves {
             // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Take the abs of the signal, then scale and shift back to
             // the [-1,1] range.
@@ -216,6 +263,33 @@ where
         // Scale the result to the [-1,1] range.
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Billow{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 4-dimensional Billow noise
@@ -225,6 +299,7 @@ where
 {
     fn get(&self, point: [f64; 4]) -> f64 {
         let mut point = Vector4::from(point);
+        let octave_offset = Vector4::from(self.octave_offset);
 
         let mut result = 0.0;
 
@@ -232,7 +307,7 @@ where
 
         for x in 0..self.octaves {
             // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Take the abs of the signal, then scale and shift back to
             // the [-1,1] range.
@@ -251,4 +326,31 @@ where
         // Scale the result to the [-1,1] range.
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Billow{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
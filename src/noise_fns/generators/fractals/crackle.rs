@@ -0,0 +1,290 @@
+use crate::noise_fns::{MultiFractal, NoiseFn, Seedable, Worley, WorleyFeatures};
+use alloc::{format, string::String, vec::Vec};
+
+/// Noise function that outputs "crackle" noise, a network of sharp, branching
+/// ridges.
+///
+/// This noise function sums several octaves of Worley noise like fBm, except
+/// that each octave uses the difference between the distances to the second
+/// nearest and the nearest feature points (F2 - F1). That difference is zero
+/// along the borders between the cells, so each octave is folded into a ridge
+/// that peaks on the cell borders and falls off towards the feature points.
+/// Larger cracks from the low octaves branch into finer cracks from the high
+/// octaves, which suits dried mud, cracked rock and eroded ridge lines.
+///
+/// The distances are measured in cell space and clamped to 1.0 before being
+/// folded, so the output stays within `[-1, 1]`.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Crackle {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cells per unit length of the first octave.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    ///
+    /// The frequency of each successive octave is equal to the product of the
+    /// previous octave's frequency and the lacunarity value.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    ///
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence value. Increasing the
+    /// persistence makes the fine cracks more pronounced.
+    pub persistence: f64,
+
+    /// Translation applied to each successive octave, scaled by the octave's
+    /// index. The default offset is zero.
+    #[serde(default)]
+    pub octave_offset: [f64; 4],
+
+    seed: u32,
+    sources: Vec<Worley>,
+    scale_factor: f64,
+}
+
+impl Crackle {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 4;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            octave_offset: [0.0; 4],
+            sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            scale_factor: Self::calc_scale_factor(
+                Self::DEFAULT_PERSISTENCE,
+                Self::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
+        let denom = (1..=octaves).fold(0.0, |acc, x| acc + persistence.powi(x as i32));
+
+        1.0 / denom
+    }
+
+    /// Sets the Worley functions used for the octaves, for example to change
+    /// their distance function. Their frequency should be left at 1.0, since
+    /// the frequency of each octave is set by the fractal.
+    pub fn set_sources(self, sources: Vec<Worley>) -> Self {
+        Self { sources, ..self }
+    }
+}
+
+impl Default for Crackle {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl MultiFractal for Crackle {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves),
+            scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            scale_factor: Self::calc_scale_factor(persistence, self.octaves),
+            ..self
+        }
+    }
+
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        Self {
+            octave_offset: super::pad_octave_offset(offset),
+            ..self
+        }
+    }
+}
+
+impl Seedable for Crackle {
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// Folds the distance between the two nearest feature points into a ridge in
+/// `[-1, 1]` that peaks where the distance is zero, on the cell borders.
+#[inline]
+fn crack(border_distance: f64) -> f64 {
+    let ridge = 1.0 - border_distance.min(1.0);
+
+    ridge * ridge * 2.0 - 1.0
+}
+
+impl<const DIM: usize> NoiseFn<f64, DIM> for Crackle
+where
+    Worley: WorleyFeatures<DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut point = point.map(|value| value * self.frequency);
+
+        let mut result = 0.0;
+
+        for (x, source) in self.sources.iter().take(self.octaves).enumerate() {
+            let mut octave_point = point;
+            for (value, offset) in octave_point.iter_mut().zip(&self.octave_offset) {
+                *value += offset * x as f64;
+            }
+
+            // Get the signal from the distances to the two nearest feature
+            // points.
+            let [f1, f2] = source.get_k_nearest::<DIM, 2>(octave_point);
+            let mut signal = crack(f2 - f1);
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= self.persistence.powi((x as i32) + 1);
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            point = point.map(|value| value * self.lacunarity);
+        }
+
+        // Scale the result to the [-1,1] range.
+        result * self.scale_factor
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        // Each octave visits every cell within the search radius of the cell
+        // of the input value.
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| (2 * source.search_radius as u32 + 1).saturating_pow(DIM as u32))
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Crackle{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_concentrates_near_cell_borders() {
+        let crackle = Crackle::new(9).set_octaves(3).set_frequency(0.5);
+
+        // For every sample, record the output and the distance to the nearest
+        // cell border over all octaves.
+        let mut samples = [(0.0, 0.0); 4096];
+        for (i, sample) in samples.iter_mut().enumerate() {
+            let point = [(i % 64) as f64 * 0.13, (i / 64) as f64 * 0.13];
+
+            let mut border_distance = f64::INFINITY;
+            let mut scale = crackle.frequency;
+            for source in &crackle.sources[..crackle.octaves] {
+                let [f1, f2] = source.get_k_nearest::<2, 2>(point.map(|value| value * scale));
+                border_distance = border_distance.min(f2 - f1);
+                scale *= crackle.lacunarity;
+            }
+
+            *sample = (crackle.get(point), border_distance);
+        }
+
+        let mean = |samples: &[(f64, f64)]| {
+            samples.iter().map(|sample| sample.1).sum::<f64>() / samples.len() as f64
+        };
+        let overall = mean(&samples);
+
+        // The brightest tenth of the output lies much closer to the cell
+        // borders than the samples as a whole.
+        samples.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+        let brightest = mean(&samples[..samples.len() / 10]);
+
+        assert!(
+            brightest < overall * 0.5,
+            "{} not below half of {}",
+            brightest,
+            overall
+        );
+        assert!(samples.iter().all(|sample| sample.0.abs() <= 1.0));
+    }
+
+    #[test]
+    fn sample_cost_counts_searched_cells() {
+        let crackle = Crackle::new(0).set_octaves(3);
+        let radius = Worley::K_NEAREST_SEARCH_RADIUS as u32;
+
+        assert_eq!(
+            3 * (2 * radius + 1).pow(2),
+            NoiseFn::<f64, 2>::sample_cost(&crackle)
+        );
+        assert_eq!(
+            3 * (2 * radius + 1).pow(4),
+            NoiseFn::<f64, 4>::sample_cost(&crackle)
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    math::vectors::*,
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{interpolate, s_curve::cubic::Cubic},
+    noise_fns::{MultiFractal, NoiseFn, OctaveTransform, Seedable},
 };
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 /// Noise function that outputs fBm (fractal Brownian motion) noise.
 ///
@@ -32,6 +32,11 @@ pub struct Fbm<T> {
     /// The number of cycles per unit length that the noise function outputs.
     pub frequency: f64,
 
+    /// Multiplier of the frequency along each axis. Axes beyond the dimension
+    /// of the input value are ignored. The default is 1.0 on every axis.
+    #[serde(default = "super::super::default_axis_frequency")]
+    pub axis_frequency: [f64; 4],
+
     /// A multiplier that determines how quickly the frequency increases for
     /// each successive octave in the noise function.
     ///
@@ -50,9 +55,24 @@ pub struct Fbm<T> {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
+    /// Translation applied to each successive octave, scaled by the octave's
+    /// index. Offsetting the octaves makes each one sample an uncorrelated
+    /// region of its source function. The default offset is zero.
+    #[serde(default)]
+    pub octave_offset: [f64; 4],
+
+    /// Transform applied to each octave before the octaves are summed. The
+    /// default is `OctaveTransform::None`.
+    #[serde(default = "super::default_octave_transform")]
+    pub octave_transform: OctaveTransform,
+
     seed: u32,
     sources: Vec<T>,
     scale_factor: f64,
+
+    /// Precomputed textures of the sources, if approximation is enabled.
+    #[serde(skip)]
+    textures: Option<Vec<OctaveTexture>>,
 }
 
 fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
@@ -75,15 +95,138 @@ where
             seed,
             octaves: Self::DEFAULT_OCTAVE_COUNT,
             frequency: Self::DEFAULT_FREQUENCY,
+            axis_frequency: super::super::default_axis_frequency(),
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            octave_offset: [0.0; 4],
+            octave_transform: OctaveTransform::None,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
             scale_factor: calc_scale_factor(Self::DEFAULT_PERSISTENCE, Self::DEFAULT_OCTAVE_COUNT),
+            textures: None,
         }
     }
 
+    /// Multiplies the frequency by a separate factor along each axis, which
+    /// stretches the noise independently along each axis. Axes that are not
+    /// given keep a factor of 1.0.
+    ///
+    /// The factors combine with the frequency, so setting every factor to `f`
+    /// with the default frequency of 1.0 matches `set_frequency(f)`.
+    pub fn set_frequency_per_axis<const DIM: usize>(self, frequency: [f64; DIM]) -> Self {
+        Self {
+            axis_frequency: super::super::pad_axis_frequency(frequency),
+            ..self
+        }
+    }
+
+    /// Replaces the sources, discarding any approximation textures.
     pub fn set_sources(self, sources: Vec<T>) -> Self {
-        Self { sources, ..self }
+        Self {
+            sources,
+            textures: None,
+            ..self
+        }
+    }
+
+    /// Enables or disables approximating the 2-dimensional output with
+    /// precomputed textures, for preview-quality output at a fraction of the
+    /// cost. Disabled by default.
+    ///
+    /// Enabling approximation samples each octave's source once into a small
+    /// texture, 32 lattice cells square and centered on the origin. The
+    /// 2-dimensional `get` and `get_with_lod` then read the octaves from the
+    /// textures with bilinear filtering, instead of evaluating the sources. The
+    /// textures wrap around, so each octave repeats every 32 units of its own
+    /// coordinates, and is blended across the last unit before the wrap to hide
+    /// the seam.
+    /// The 3- and 4-dimensional outputs are unaffected.
+    ///
+    /// Changing the seed, the octave count or the sources discards the
+    /// textures, so approximation should be enabled once the fractal is
+    /// configured. The textures are not serialized either, so a deserialized
+    /// fractal is exact until approximation is enabled again.
+    pub fn set_approximate(self, approximate: bool) -> Self
+    where
+        T: NoiseFn<f64, 2>,
+    {
+        let textures = if approximate {
+            Some(
+                self.sources
+                    .iter()
+                    .take(self.octaves)
+                    .map(OctaveTexture::new)
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        Self { textures, ..self }
+    }
+
+    /// Returns a noise function that outputs only the octave at `index`, with
+    /// its frequency, amplitude and transform applied, for inspecting how much
+    /// each octave contributes.
+    ///
+    /// The outputs of the octaves sum to the output of the fractal, so each
+    /// octave can be rendered with the usual map builders to find the
+    /// frequencies that dominate. The octaves always evaluate their sources,
+    /// even if approximation is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `octaves`.
+    pub fn octave_source(&self, index: usize) -> FbmOctave<'_, T> {
+        assert!(
+            index < self.octaves,
+            "octave {} out of range for an Fbm with {} octaves",
+            index,
+            self.octaves
+        );
+
+        FbmOctave { fbm: self, index }
+    }
+
+    /// Returns true if the 2-dimensional output is approximated with
+    /// precomputed textures.
+    pub fn is_approximate(&self) -> bool {
+        self.textures.is_some()
+    }
+
+    /// Sets the transform applied to each octave before the octaves are
+    /// summed.
+    ///
+    /// `OctaveTransform::Abs` applies the octave transform of `Billow`. Note
+    /// that `Fbm` weights its octaves differently from `Billow` beyond the
+    /// second octave, so the two only produce identical output for up to two
+    /// octaves, or a persistence of 1.0.
+    pub fn set_octave_transform(self, octave_transform: OctaveTransform) -> Self {
+        Self {
+            octave_transform,
+            ..self
+        }
+    }
+
+    /// Returns the output for the input value using only as many octaves as
+    /// the level of detail allows, which saves time for distant regions of a
+    /// large terrain.
+    ///
+    /// `lod` is the effective octave count, clamped between one and
+    /// `octaves`. The fractional part fades in the next octave, so the output
+    /// changes smoothly as the level of detail varies and reaches the output of
+    /// `get` once `lod` equals `octaves`. The omitted octaves are the finest
+    /// and weakest ones, so lower levels only lose small-scale detail.
+    ///
+    /// To vary the level of detail per pixel, build the map from a closure
+    /// with `PlaneMapBuilder::new_fn`, deriving `lod` from the distance to the
+    /// viewer.
+    pub fn get_with_lod<const DIM: usize>(&self, point: [f64; DIM], lod: f64) -> f64
+    where
+        T: NoiseFn<f64, DIM>,
+    {
+        let lod = lod.clamp(1.0, self.octaves as f64);
+
+        self.sum_octaves(point, lod)
     }
 
     fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
@@ -93,6 +236,72 @@ where
     }
 }
 
+impl<T> Fbm<T> {
+    /// Returns the output of the source of the octave at `index`, from its
+    /// approximation texture if there is one and the point is 2-dimensional.
+    fn octave_signal<const DIM: usize>(&self, index: usize, point: [f64; DIM]) -> f64
+    where
+        T: NoiseFn<f64, DIM>,
+    {
+        match &self.textures {
+            Some(textures) if DIM == 2 => textures[index].get([point[0], point[1]]),
+            _ => self.sources[index].get(point),
+        }
+    }
+
+    /// Sums the octaves at the input value, as `get` does. Only the first
+    /// `lod` octaves are summed, and a fractional part of `lod` fades in the
+    /// last of them.
+    fn sum_octaves<const DIM: usize>(&self, point: [f64; DIM], lod: f64) -> f64
+    where
+        T: NoiseFn<f64, DIM>,
+    {
+        let mut point = super::super::scale_axes(point, &self.axis_frequency);
+
+        let mut result = 0.0;
+
+        let mut attenuation = self.persistence;
+
+        for value in point.iter_mut() {
+            *value *= self.frequency;
+        }
+
+        for x in 0..lod.ceil() as usize {
+            let mut octave_point = point;
+            for (value, offset) in octave_point.iter_mut().zip(self.octave_offset.iter()) {
+                *value += offset * x as f64;
+            }
+
+            // Get the signal and apply the octave transform.
+            let mut signal = self
+                .octave_transform
+                .apply(self.octave_signal(x, octave_point));
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= attenuation;
+
+            // Fade in the partial octave.
+            if x as f64 + 1.0 > lod {
+                signal *= lod - x as f64;
+            }
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= attenuation;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            for value in point.iter_mut() {
+                *value *= self.lacunarity;
+            }
+        }
+
+        // Scale the result into the [-1,1] range
+        result * self.scale_factor
+    }
+}
+
 impl<T> Default for Fbm<T>
 where
     T: Default + Seedable,
@@ -116,6 +325,7 @@ where
             octaves,
             sources: super::build_sources(self.seed, octaves),
             scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            textures: None,
             ..self
         }
     }
@@ -135,6 +345,13 @@ where
             ..self
         }
     }
+
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        Self {
+            octave_offset: super::pad_octave_offset(offset),
+            ..self
+        }
+    }
 }
 
 impl<T> Seedable for Fbm<T>
@@ -149,6 +366,7 @@ where
         Self {
             seed,
             sources: super::build_sources(seed, self.octaves),
+            textures: None,
             ..self
         }
     }
@@ -158,75 +376,242 @@ where
     }
 }
 
-/// 2-dimensional Fbm noise
-impl<T> NoiseFn<f64, 2> for Fbm<T>
+/// Noise function that outputs a single octave of an [`Fbm`], returned by
+/// [`Fbm::octave_source`].
+#[derive(Debug)]
+pub struct FbmOctave<'a, T> {
+    fbm: &'a Fbm<T>,
+    index: usize,
+}
+
+impl<T> Clone for FbmOctave<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FbmOctave<'_, T> {}
+
+impl<T> FbmOctave<'_, T> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the frequency of the octave, relative to the input value.
+    pub fn frequency(&self) -> f64 {
+        (0..self.index).fold(self.fbm.frequency, |frequency, _| {
+            frequency * self.fbm.lacunarity
+        })
+    }
+
+    /// Returns the weight of the octave in the output of the fractal.
+    pub fn amplitude(&self) -> f64 {
+        let attenuation = (0..self.index).fold(self.fbm.persistence, |attenuation, _| {
+            attenuation * attenuation
+        });
+
+        attenuation * self.fbm.scale_factor
+    }
+}
+
+impl<T, const DIM: usize> NoiseFn<f64, DIM> for FbmOctave<'_, T>
 where
-    T: NoiseFn<f64, 2>,
+    T: NoiseFn<f64, DIM>,
 {
-    fn get(&self, point: [f64; 2]) -> f64 {
-        let mut point = Vector2::from(point);
-
-        let mut result = 0.0;
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let fbm = self.fbm;
+
+        // Scale the input value in the same steps as the fractal does, so
+        // that the octaves add up to its output.
+        let mut point = super::super::scale_axes(point, &fbm.axis_frequency);
+        for (axis, value) in point.iter_mut().enumerate() {
+            *value *= fbm.frequency;
+            for _ in 0..self.index {
+                *value *= fbm.lacunarity;
+            }
+            *value += fbm.octave_offset[axis] * self.index as f64;
+        }
 
-        let mut attenuation = self.persistence;
+        let signal = fbm
+            .octave_transform
+            .apply(fbm.sources[self.index].get(point));
 
-        point *= self.frequency;
+        signal * self.amplitude()
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+    fn sample_cost(&self) -> u32 {
+        self.fbm.sources[self.index].sample_cost()
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency() * super::super::max_axis_frequency::<DIM>(&self.fbm.axis_frequency))
+    }
 
-            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
-            attenuation *= attenuation;
+    fn describe(&self) -> String {
+        format!(
+            "FbmOctave{{index:{}, frequency:{}}}",
+            self.index,
+            self.frequency()
+        )
+    }
+}
 
-            // Add the signal to the result.
-            result += signal;
+/// Samples of a source function over a square of lattice cells centered on
+/// the origin, used to approximate the source in [`Fbm::set_approximate`].
+#[derive(Clone, Debug)]
+struct OctaveTexture {
+    values: Vec<f32>,
+}
 
-            // Increase the frequency for the next octave.
-            point *= self.lacunarity;
+impl OctaveTexture {
+    /// Number of lattice cells along each side of the texture.
+    const SIZE: usize = 32;
+
+    /// Number of samples per lattice cell along each axis.
+    const RESOLUTION: usize = 4;
+
+    /// Number of samples along each side of the texture.
+    const SAMPLES: usize = Self::SIZE * Self::RESOLUTION;
+
+    fn new<Source>(source: &Source) -> Self
+    where
+        Source: NoiseFn<f64, 2>,
+    {
+        let size = Self::SIZE as f64;
+        let origin = size / 2.0;
+        let mut values = Vec::with_capacity(Self::SAMPLES * Self::SAMPLES);
+
+        for v in 0..Self::SAMPLES {
+            let y = v as f64 / Self::RESOLUTION as f64 - origin;
+            // Blend toward the start of the texture over the last cell, so
+            // that the texture wraps without a seam.
+            let y_blend = (y - (origin - 1.0)).max(0.0);
+
+            for u in 0..Self::SAMPLES {
+                let x = u as f64 / Self::RESOLUTION as f64 - origin;
+                let x_blend = (x - (origin - 1.0)).max(0.0);
+
+                let value = if x_blend > 0.0 || y_blend > 0.0 {
+                    let x_blend = x_blend.map_cubic();
+                    let y_blend = y_blend.map_cubic();
+                    interpolate::linear(
+                        interpolate::linear(source.get([x, y]), source.get([x - size, y]), x_blend),
+                        interpolate::linear(
+                            source.get([x, y - size]),
+                            source.get([x - size, y - size]),
+                            x_blend,
+                        ),
+                        y_blend,
+                    )
+                } else {
+                    source.get([x, y])
+                };
+
+                values.push(value as f32);
+            }
         }
 
-        // Scale the result into the [-1,1] range
-        result * self.scale_factor
+        Self { values }
+    }
+
+    #[inline]
+    fn get(&self, point: [f64; 2]) -> f64 {
+        const MASK: usize = OctaveTexture::SAMPLES - 1;
+
+        let origin = (Self::SIZE / 2) as f64;
+        let x = (point[0] + origin) * Self::RESOLUTION as f64;
+        let y = (point[1] + origin) * Self::RESOLUTION as f64;
+        let (x_floor, y_floor) = (x.floor(), y.floor());
+        let (x_alpha, y_alpha) = (x - x_floor, y - y_floor);
+
+        // The texture has a power of two side, so wrapping is a mask.
+        let x0 = (x_floor as isize as usize) & MASK;
+        let y0 = (y_floor as isize as usize) & MASK;
+        let x1 = (x0 + 1) & MASK;
+        let y1 = (y0 + 1) & MASK;
+
+        let row0 = &self.values[y0 * Self::SAMPLES..][..Self::SAMPLES];
+        let row1 = &self.values[y1 * Self::SAMPLES..][..Self::SAMPLES];
+
+        interpolate::linear(
+            interpolate::linear(row0[x0] as f64, row0[x1] as f64, x_alpha),
+            interpolate::linear(row1[x0] as f64, row1[x1] as f64, x_alpha),
+            y_alpha,
+        )
     }
 }
 
-/// 3-dimensional Fbm noise
-impl<T> NoiseFn<f64, 3> for Fbm<T>
+/// 2-dimensional Fbm noise
+impl<T> NoiseFn<f64, 2> for Fbm<T>
 where
-    T: NoiseFn<f64, 3>,
+    T: NoiseFn<f64, 2>,
 {
-    fn get(&self, point: [f64; 3]) -> f64 {
-        let mut point = Vector3::from(point);
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.sum_octaves(point, self.octaves as f64)
+    }
 
-        let mut result = 0.0;
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
 
-        let mut attenuation = self.persistence;
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency * super::super::max_axis_frequency::<2>(&self.axis_frequency))
+    }
 
-        point *= self.frequency;
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency * super::super::max_axis_frequency::<2>(&self.axis_frequency),
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+    fn describe(&self) -> String {
+        format!(
+            "Fbm{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+/// 3-dimensional Fbm noise
+impl<T> NoiseFn<f64, 3> for Fbm<T>
+where
+    T: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.sum_octaves(point, self.octaves as f64)
+    }
 
-            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
-            attenuation *= attenuation;
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
 
-            // Add the signal to the result.
-            result += signal;
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency * super::super::max_axis_frequency::<3>(&self.axis_frequency))
+    }
 
-            // Increase the frequency for the next octave.
-            point *= self.lacunarity;
-        }
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency * super::super::max_axis_frequency::<3>(&self.axis_frequency),
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
 
-        // Scale the result into the [-1,1] range
-        result * self.scale_factor
+    fn describe(&self) -> String {
+        format!(
+            "Fbm{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
     }
 }
 
@@ -236,32 +621,227 @@ where
     T: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        let mut point = Vector4::from(point);
+        self.sum_octaves(point, self.octaves as f64)
+    }
 
-        let mut result = 0.0;
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
 
-        let mut attenuation = self.persistence;
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency * super::super::max_axis_frequency::<4>(&self.axis_frequency))
+    }
 
-        point *= self.frequency;
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency * super::super::max_axis_frequency::<4>(&self.axis_frequency),
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+    fn describe(&self) -> String {
+        format!(
+            "Fbm{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Billow, Perlin};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn abs_transform_matches_billow() {
+        let fbm = Fbm::<Perlin>::new(7).set_octave_transform(OctaveTransform::Abs);
+        let billow = Billow::<Perlin>::new(7);
+
+        let configurations = [
+            (fbm.clone().set_octaves(2), billow.clone().set_octaves(2)),
+            (
+                fbm.set_persistence(1.0).set_octaves(5),
+                billow.set_persistence(1.0).set_octaves(5),
+            ),
+        ];
+
+        for (fbm, billow) in configurations.iter() {
+            for i in 0..50 {
+                let point = [i as f64 * 0.173, i as f64 * -0.311, 0.25];
+                assert!((fbm.get(point) - billow.get(point)).abs() < 1e-12);
+            }
+        }
+    }
 
-            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
-            attenuation *= attenuation;
+    /// Pearson correlation between an Fbm built from two identical octaves
+    /// and its first octave on its own.
+    fn octave_correlation(offset: [f64; 2]) -> f64 {
+        let perlin = Perlin::new(0);
+        let fbm = Fbm::<Perlin>::new(0)
+            .set_octaves(2)
+            .set_lacunarity(1.0)
+            .set_octave_offset(offset)
+            .set_sources(vec![perlin; 2]);
+
+        let samples: Vec<(f64, f64)> = (0..2500)
+            .map(|i| {
+                let point = [(i % 50) as f64 * 0.137, (i / 50) as f64 * 0.151];
+                (fbm.get(point), perlin.get(point))
+            })
+            .collect();
+
+        let count = samples.len() as f64;
+        let mean_a = samples.iter().map(|s| s.0).sum::<f64>() / count;
+        let mean_b = samples.iter().map(|s| s.1).sum::<f64>() / count;
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for (a, b) in samples {
+            cov += (a - mean_a) * (b - mean_b);
+            var_a += (a - mean_a) * (a - mean_a);
+            var_b += (b - mean_b) * (b - mean_b);
+        }
 
-            // Add the signal to the result.
-            result += signal;
+        cov / (var_a * var_b).sqrt()
+    }
 
-            // Increase the frequency for the next octave.
-            point *= self.lacunarity;
+    #[test]
+    fn octave_offset_decorrelates_octaves() {
+        let without_offset = octave_correlation([0.0, 0.0]);
+        let with_offset = octave_correlation([1013.7, 731.3]);
+
+        assert!(without_offset > 0.999);
+        assert!(with_offset < 0.95, "correlation: {}", with_offset);
+    }
+
+    static SAMPLES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Perlin source that counts how often it is sampled.
+    #[derive(Clone, Default)]
+    struct CountingPerlin(Perlin);
+
+    impl Seedable for CountingPerlin {
+        fn set_seed(self, seed: u32) -> Self {
+            Self(self.0.set_seed(seed))
         }
 
-        // Scale the result into the [-1,1] range
-        result * self.scale_factor
+        fn seed(&self) -> u32 {
+            self.0.seed()
+        }
+    }
+
+    impl NoiseFn<f64, 2> for CountingPerlin {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            SAMPLES.fetch_add(1, Ordering::Relaxed);
+            self.0.get(point)
+        }
+    }
+
+    #[test]
+    fn lod_reduces_octaves_smoothly() {
+        let fbm = Fbm::<CountingPerlin>::new(3).set_octaves(8);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * 0.291];
+            assert_eq!(fbm.get(point), fbm.get_with_lod(point, 8.0));
+            assert_eq!(fbm.get(point), fbm.get_with_lod(point, 20.0));
+
+            // Dropping the finest octaves only changes the output slightly.
+            assert!((fbm.get(point) - fbm.get_with_lod(point, 4.0)).abs() < 0.05);
+
+            // The partial octave fades in without popping.
+            let below = fbm.get_with_lod(point, 4.999);
+            let above = fbm.get_with_lod(point, 5.0);
+            assert!((below - above).abs() < 1e-3);
+        }
+
+        let point = [0.37, 1.91];
+        SAMPLES.store(0, Ordering::Relaxed);
+        fbm.get_with_lod(point, 8.0);
+        let full = SAMPLES.swap(0, Ordering::Relaxed);
+        fbm.get_with_lod(point, 3.0);
+        let reduced = SAMPLES.load(Ordering::Relaxed);
+        assert_eq!((8, 3), (full, reduced));
+    }
+
+    #[test]
+    fn approximation_correlates_with_exact() {
+        let exact = Fbm::<Perlin>::new(5).set_octaves(5);
+        let approximate = exact.clone().set_approximate(true);
+        assert!(approximate.is_approximate());
+
+        let samples: Vec<(f64, f64)> = (0..2500)
+            .map(|i| {
+                let point = [(i % 50) as f64 * 0.093 - 2.0, (i / 50) as f64 * 0.087];
+                (exact.get(point), approximate.get(point))
+            })
+            .collect();
+
+        let count = samples.len() as f64;
+        let mean_a = samples.iter().map(|s| s.0).sum::<f64>() / count;
+        let mean_b = samples.iter().map(|s| s.1).sum::<f64>() / count;
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for (a, b) in samples {
+            cov += (a - mean_a) * (b - mean_b);
+            var_a += (a - mean_a) * (a - mean_a);
+            var_b += (b - mean_b) * (b - mean_b);
+        }
+        let correlation = cov / (var_a * var_b).sqrt();
+
+        assert!(correlation > 0.98, "correlation: {}", correlation);
+
+        // The level of detail reads the same textures.
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311];
+            assert_eq!(approximate.get(point), approximate.get_with_lod(point, 5.0));
+        }
+
+        // Reconfiguring the sources discards the textures.
+        assert!(!approximate.clone().set_seed(6).is_approximate());
+
+        // So does a round trip through serde, until approximation is enabled
+        // again.
+        let json = serde_json::to_string(&approximate).unwrap();
+        let loaded: Fbm<Perlin> = serde_json::from_str(&json).unwrap();
+        assert!(!loaded.is_approximate());
+        let loaded = loaded.set_approximate(true);
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311];
+            assert_eq!(approximate.get(point), loaded.get(point));
+        }
+    }
+
+    #[test]
+    fn equal_axis_frequencies_match_scalar_frequency() {
+        let scalar = Fbm::<Perlin>::new(3).set_frequency(2.5);
+        let per_axis = Fbm::<Perlin>::new(3).set_frequency_per_axis([2.5, 2.5]);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311];
+            assert_eq!(scalar.get(point), per_axis.get(point));
+        }
+    }
+
+    #[test]
+    fn octave_sources_sum_to_output() {
+        let fbm = Fbm::<Perlin>::new(12)
+            .set_octaves(5)
+            .set_frequency(1.3)
+            .set_persistence(0.6)
+            .set_octave_offset([3.7, -1.9, 0.4])
+            .set_octave_transform(OctaveTransform::Ridge);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311, i as f64 * 0.05];
+            let sum: f64 = (0..fbm.octaves)
+                .map(|index| fbm.octave_source(index).get(point))
+                .sum();
+
+            assert!((fbm.get(point) - sum).abs() < 1e-12);
+        }
     }
 }
@@ -2,7 +2,7 @@ use crate::{
     math::vectors::*,
     noise_fns::{MultiFractal, NoiseFn, Seedable},
 };
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 /// Noise function that outputs hybrid Multifractal noise.
 ///
@@ -38,6 +38,12 @@ pub struct HybridMulti<T> {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
+    /// Translation applied to each successive octave, scaled by the octave's
+    /// index. Offsetting the octaves makes each one sample an uncorrelated
+    /// region of its source function. The default offset is zero.
+    #[serde(default)]
+    pub octave_offset: [f64; 4],
+
     seed: u32,
     sources: Vec<T>,
     scale_factor: f64,
@@ -61,6 +67,7 @@ where
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            octave_offset: [0.0; 4],
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVES),
             scale_factor: Self::calc_scale_factor(Self::DEFAULT_PERSISTENCE, Self::DEFAULT_OCTAVES),
         }
@@ -137,6 +144,13 @@ where
             ..self
         }
     }
+
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        Self {
+            octave_offset: super::pad_octave_offset(offset),
+            ..self
+        }
+    }
 }
 
 impl<T> Seedable for HybridMulti<T>
@@ -167,6 +181,7 @@ where
 {
     fn get(&self, point: [f64; 2]) -> f64 {
         let mut point = Vector2::from(point);
+        let octave_offset = Vector2::new(self.octave_offset[0], self.octave_offset[1]);
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
@@ -182,7 +197,7 @@ where
             point *= self.lacunarity;
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Scale the amplitude appropriately for this frequency.
             signal *= self.persistence.powi(x as i32);
@@ -197,6 +212,33 @@ where
         // Scale the result to the [-1,1] range
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "HybridMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 3-dimensional `HybridMulti` noise
@@ -206,6 +248,11 @@ where
 {
     fn get(&self, point: [f64; 3]) -> f64 {
         let mut point = Vector3::from(point);
+        let octave_offset = Vector3::new(
+            self.octave_offset[0],
+            self.octave_offset[1],
+            self.octave_offset[2],
+        );
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
@@ -221,7 +268,7 @@ where
             point *= self.lacunarity;
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Scale the amplitude appropriately for this frequency.
             signal *= self.persistence.powi(x as i32);
@@ -236,6 +283,33 @@ where
         // Scale the result to the [-1,1] range
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "HybridMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 4-dimensional `HybridMulti` noise
@@ -245,6 +319,7 @@ where
 {
     fn get(&self, point: [f64; 4]) -> f64 {
         let mut point = Vector4::from(point);
+        let octave_offset = Vector4::from(self.octave_offset);
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
@@ -260,7 +335,7 @@ where
             point *= self.lacunarity;
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Scale the amplitude appropriately for this frequency.
             signal *= self.persistence.powi(x as i32);
@@ -275,4 +350,31 @@ where
         // Scale the result to the [-1,1] range
         result * self.scale_factor
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "HybridMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
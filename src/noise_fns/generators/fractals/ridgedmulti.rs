@@ -2,7 +2,7 @@ use crate::{
     math::vectors::*,
     noise_fns::{MultiFractal, NoiseFn, Seedable},
 };
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 /// Noise function that outputs ridged-multifractal noise.
 ///
@@ -56,6 +56,12 @@ pub struct RidgedMulti<T> {
     /// half the height of the previous.
     pub attenuation: f64,
 
+    /// Translation applied to each successive octave, scaled by the octave's
+    /// index. Offsetting the octaves makes each one sample an uncorrelated
+    /// region of its source function. The default offset is zero.
+    #[serde(default)]
+    pub octave_offset: [f64; 4],
+
     seed: u32,
     sources: Vec<T>,
     scale_factor: f64,
@@ -81,6 +87,7 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             attenuation: Self::DEFAULT_ATTENUATION,
+            octave_offset: [0.0; 4],
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
@@ -167,6 +174,13 @@ where
             ..self
         }
     }
+
+    fn set_octave_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        Self {
+            octave_offset: super::pad_octave_offset(offset),
+            ..self
+        }
+    }
 }
 
 impl<T> Seedable for RidgedMulti<T>
@@ -197,6 +211,7 @@ where
 {
     fn get(&self, point: [f64; 2]) -> f64 {
         let mut point = Vector2::from(point);
+        let octave_offset = Vector2::new(self.octave_offset[0], self.octave_offset[1]);
 
         let mut result = 0.0;
         let mut weight = 1.0;
@@ -205,7 +220,7 @@ where
 
         for x in 0..self.octaves {
             // Get the value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Make the ridges.
             signal = signal.abs();
@@ -247,6 +262,33 @@ where
         // Shift the result to [-1, 1]
         result - 1.0
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "RidgedMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 3-dimensional `RidgedMulti` noise
@@ -256,6 +298,11 @@ where
 {
     fn get(&self, point: [f64; 3]) -> f64 {
         let mut point = Vector3::from(point);
+        let octave_offset = Vector3::new(
+            self.octave_offset[0],
+            self.octave_offset[1],
+            self.octave_offset[2],
+        );
 
         let mut result = 0.0;
         let mut weight = 1.0;
@@ -264,7 +311,7 @@ where
 
         for x in 0..self.octaves {
             // Get the value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Make the ridges.
             signal = signal.abs();
@@ -306,6 +353,33 @@ where
         // Shift the result to [-1, 1]
         result - 1.0
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "RidgedMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
 
 /// 4-dimensional `RidgedMulti` noise
@@ -315,6 +389,7 @@ where
 {
     fn get(&self, point: [f64; 4]) -> f64 {
         let mut point = Vector4::from(point);
+        let octave_offset = Vector4::from(self.octave_offset);
 
         let mut result = 0.0;
         let mut weight = 1.0;
@@ -323,7 +398,7 @@ where
 
         for x in 0..self.octaves {
             // Get the value.
-            let mut signal = self.sources[x].get(point.into_array());
+            let mut signal = self.sources[x].get((point + octave_offset * x as f64).into_array());
 
             // Make the ridges.
             signal = signal.abs();
@@ -365,4 +440,31 @@ where
         // Shift the result to [-1, 1]
         result - 1.0
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.sources
+            .iter()
+            .take(self.octaves)
+            .map(|source| source.sample_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        Some(super::highest_octave_frequency(
+            self.frequency,
+            self.lacunarity,
+            self.octaves,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "RidgedMulti{{octaves:{}, frequency:{}}}",
+            self.octaves, self.frequency
+        )
+    }
 }
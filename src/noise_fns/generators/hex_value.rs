@@ -0,0 +1,115 @@
+use crate::{
+    core::hex_value::hex_value_2d,
+    noise_fns::{NoiseFn, Seedable},
+    permutationtable::PermutationTable,
+};
+
+/// Noise function that outputs 2-dimensional Value noise on a hexagonal
+/// lattice.
+///
+/// Square-lattice [`Value`](crate::Value) noise shows visible axis-aligned
+/// artifacts. Interpolating between the three nearest points of a hexagonal
+/// (triangular) lattice instead produces a much more isotropic result.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct HexValue {
+    seed: u32,
+    perm_table: PermutationTable,
+}
+
+impl HexValue {
+    pub const DEFAULT_SEED: u32 = 0;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+        }
+    }
+}
+
+impl Default for HexValue {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for HexValue {
+    /// Sets the seed value for HexValue noise
+    fn set_seed(self, seed: u32) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// 2-dimensional hexagonal value noise
+impl NoiseFn<f64, 2> for HexValue {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        hex_value_2d(point.into(), &self.perm_table)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+
+    /// Strength of the four-fold (axis-aligned) component of the gradient
+    /// direction distribution, relative to the total gradient power. Square
+    /// lattices produce gradients that bunch up along the axes, while an
+    /// isotropic field returns a value close to 0.0.
+    fn axis_alignment<F: NoiseFn<f64, 2>>(source: &F) -> f64 {
+        const STEP: f64 = 0.01;
+
+        let mut total = 0.0;
+        let mut four_fold = 0.0;
+        for i in 0..300 {
+            for j in 0..300 {
+                let x = i as f64 * 0.173 + 0.05;
+                let y = j as f64 * 0.191 + 0.05;
+
+                let gx = source.get([x + STEP, y]) - source.get([x - STEP, y]);
+                let gy = source.get([x, y + STEP]) - source.get([x, y - STEP]);
+                let power = gx * gx + gy * gy;
+
+                total += power;
+                four_fold += power * (4.0 * gy.atan2(gx)).cos();
+            }
+        }
+
+        four_fold.abs() / total
+    }
+
+    #[test]
+    fn hex_value_is_more_isotropic_than_value() {
+        let hex = axis_alignment(&HexValue::new(0));
+        let square = axis_alignment(&Value::new(0));
+
+        assert!(hex * 4.0 < square, "hex: {}, square: {}", hex, square);
+    }
+
+    #[test]
+    fn hex_value_stays_in_range() {
+        let hex = HexValue::new(4);
+
+        for i in 0..1000 {
+            let value = hex.get([i as f64 * 0.137, i as f64 * -0.071]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+}
@@ -7,21 +7,65 @@ use crate::{
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
+use alloc::{format, string::String};
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub struct OpenSimplex {
     seed: u32,
     perm_table: PermutationTable,
+    #[serde(default)]
+    normalization: bool,
 }
 
 impl OpenSimplex {
     const DEFAULT_SEED: u32 = 0;
 
+    /// Factor applied to 2-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±0.544.
+    pub const SCALE_FACTOR_2D: f64 = 1.838;
+
+    /// Factor applied to 3-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±0.538.
+    pub const SCALE_FACTOR_3D: f64 = 1.86;
+
+    /// Factor applied to 4-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±1.08, so this shrinks the output.
+    pub const SCALE_FACTOR_4D: f64 = 0.925;
+
     pub fn new(seed: u32) -> Self {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            normalization: false,
+        }
+    }
+
+    /// Enables or disables scaling the output to fill the range `[-1, 1]`.
+    ///
+    /// The raw output of OpenSimplex noise covers only about half of the range
+    /// in 2 and 3 dimensions, and slightly overshoots it in 4 dimensions. With
+    /// the normalization enabled, the output is multiplied by
+    /// [`SCALE_FACTOR_2D`](Self::SCALE_FACTOR_2D),
+    /// [`SCALE_FACTOR_3D`](Self::SCALE_FACTOR_3D) or
+    /// [`SCALE_FACTOR_4D`](Self::SCALE_FACTOR_4D) and clamped to `[-1, 1]`.
+    /// Disabled by default, to preserve the original output.
+    pub fn with_normalization(self, normalization: bool) -> Self {
+        Self {
+            normalization,
+            ..self
+        }
+    }
+
+    pub fn normalization(&self) -> bool {
+        self.normalization
+    }
+
+    fn normalize(&self, value: f64, scale_factor: f64) -> f64 {
+        if self.normalization {
+            (value * scale_factor).clamp(-1.0, 1.0)
+        } else {
+            value
         }
     }
 }
@@ -44,6 +88,7 @@ impl Seedable for OpenSimplex {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -57,7 +102,18 @@ impl Seedable for OpenSimplex {
 /// This is a slower but higher quality form of gradient noise than `Perlin` 2D.
 impl NoiseFn<f64, 2> for OpenSimplex {
     fn get(&self, point: [f64; 2]) -> f64 {
-        open_simplex_2d(point.into(), &self.perm_table)
+        self.normalize(
+            open_simplex_2d(point.into(), &self.perm_table),
+            Self::SCALE_FACTOR_2D,
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("OpenSimplex{{seed:{}}}", self.seed)
     }
 }
 
@@ -66,7 +122,18 @@ impl NoiseFn<f64, 2> for OpenSimplex {
 /// This is a slower but higher quality form of gradient noise than `Perlin` 3D.
 impl NoiseFn<f64, 3> for OpenSimplex {
     fn get(&self, point: [f64; 3]) -> f64 {
-        open_simplex_3d(point.into(), &self.perm_table)
+        self.normalize(
+            open_simplex_3d(point.into(), &self.perm_table),
+            Self::SCALE_FACTOR_3D,
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("OpenSimplex{{seed:{}}}", self.seed)
     }
 }
 
@@ -75,6 +142,52 @@ impl NoiseFn<f64, 3> for OpenSimplex {
 /// This is a slower but higher quality form of gradient noise than `Perlin` 4D.
 impl NoiseFn<f64, 4> for OpenSimplex {
     fn get(&self, point: [f64; 4]) -> f64 {
-        open_simplex_4d(point.into(), &self.perm_table)
+        self.normalize(
+            open_simplex_4d(point.into(), &self.perm_table),
+            Self::SCALE_FACTOR_4D,
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("OpenSimplex{{seed:{}}}", self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_fills_output_range() {
+        let open_simplex = OpenSimplex::new(0).with_normalization(true);
+
+        let (mut min, mut max) = (0.0_f64, 0.0_f64);
+        for i in 0..400 {
+            for j in 0..400 {
+                let value = open_simplex.get([i as f64 * 0.137, j as f64 * 0.149]);
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+
+        for &extreme in &[-min, max] {
+            assert!(extreme > 0.9 && extreme <= 1.0, "extreme: {}", extreme);
+        }
+    }
+
+    #[test]
+    fn normalization_is_off_by_default() {
+        let open_simplex = OpenSimplex::new(0);
+        let point = [0.3, 1.7, -2.1];
+
+        assert!(!open_simplex.normalization());
+        assert_eq!(
+            open_simplex.get(point) * OpenSimplex::SCALE_FACTOR_3D,
+            open_simplex.with_normalization(true).get(point)
+        );
     }
 }
@@ -1,12 +1,31 @@
 use crate::{
     core::perlin::*,
     noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    permutationtable::{PeriodicHasher, PermutationTable},
 };
+use alloc::{format, string::String};
+use core::f64;
 
 /// Noise function that outputs 1/2/3/4-dimensional Perlin noise.
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Perlin {
+    /// Number of lattice cells after which the noise repeats along each axis,
+    /// if tiling is enabled.
+    #[serde(default)]
+    pub tile_period: Option<usize>,
+
+    /// Position of the lattice point that would otherwise lie at the origin.
+    /// Axes beyond the dimension of the input value are ignored. The default
+    /// is zero on every axis.
+    #[serde(default)]
+    pub lattice_offset: [f64; 4],
+
+    /// Number of lattice cells per unit length along each axis. Axes beyond
+    /// the dimension of the input value are ignored. The default is 1.0 on
+    /// every axis.
+    #[serde(default = "super::default_axis_frequency")]
+    pub axis_frequency: [f64; 4],
+
     seed: u32,
     perm_table: PermutationTable,
 }
@@ -16,10 +35,68 @@ impl Perlin {
 
     pub fn new(seed: u32) -> Self {
         Self {
+            tile_period: None,
+            lattice_offset: [0.0; 4],
+            axis_frequency: super::default_axis_frequency(),
             seed,
             perm_table: PermutationTable::new(seed),
         }
     }
+
+    /// Makes the output tile seamlessly every `period` lattice cells along each
+    /// axis, by wrapping the lattice coordinates with `rem_euclid(period)`
+    /// before they are hashed.
+    ///
+    /// Any positive period works, not just powers of two. A period of 1 gives
+    /// every cell the same gradients, so the same pattern repeats in each cell,
+    /// while a period larger than the sampled region behaves like untiled noise.
+    /// A period of zero disables tiling.
+    pub fn set_tileable(self, period: usize) -> Self {
+        Self {
+            tile_period: if period > 0 { Some(period) } else { None },
+            ..self
+        }
+    }
+
+    /// Shifts the lattice by `offset`, measured in input space, without
+    /// changing the frequency. The output at a point is the unshifted output
+    /// at that point minus `offset`, whatever the per-axis frequency.
+    ///
+    /// Layers that share a seed but have different offsets are decorrelated,
+    /// which is cheaper than reseeding each layer. Axes that are not given
+    /// are set to zero.
+    pub fn set_lattice_offset<const DIM: usize>(self, offset: [f64; DIM]) -> Self {
+        assert!(DIM <= 4, "Perlin supports at most four dimensions");
+
+        let mut lattice_offset = [0.0; 4];
+        lattice_offset[..DIM].copy_from_slice(&offset);
+
+        Self {
+            lattice_offset,
+            ..self
+        }
+    }
+
+    /// Sets the number of lattice cells per unit length along each axis, which
+    /// stretches the noise independently along each axis. Axes that are not
+    /// given keep a frequency of 1.0.
+    pub fn set_frequency_per_axis<const DIM: usize>(self, frequency: [f64; DIM]) -> Self {
+        Self {
+            axis_frequency: super::pad_axis_frequency(frequency),
+            ..self
+        }
+    }
+
+    /// Maps an input value into lattice space by removing the lattice offset
+    /// and then applying the per-axis frequency, so the offset is measured in
+    /// input space.
+    fn lattice_point<const DIM: usize>(&self, mut point: [f64; DIM]) -> [f64; DIM] {
+        for (value, offset) in point.iter_mut().zip(&self.lattice_offset) {
+            *value -= offset;
+        }
+
+        super::scale_axes(point, &self.axis_frequency)
+    }
 }
 
 impl Default for Perlin {
@@ -40,6 +117,7 @@ impl Seedable for Perlin {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -48,30 +126,373 @@ impl Seedable for Perlin {
     }
 }
 
+const D: f64 = f64::consts::FRAC_1_SQRT_2;
+const T: f64 = 0.577_350_269_189_625_8;
+
+/// The gradient directions used by `perlin_1d`, scaled to unit length.
+const GRADIENTS_1D: [[f64; 1]; 2] = [[1.0], [-1.0]];
+
+/// The gradient directions used by `perlin_2d`, scaled to unit length.
+#[rustfmt::skip]
+const GRADIENTS_2D: [[f64; 2]; 4] = [
+    [   D,    D], // 0
+    [  -D,    D], // 1
+    [   D,   -D], // 2
+    [  -D,   -D], // 3
+];
+
+/// The gradient directions used by `perlin_3d`, scaled to unit length.
+#[rustfmt::skip]
+const GRADIENTS_3D: [[f64; 3]; 16] = [
+    [   D,    D,  0.0], // 0
+    [  -D,    D,  0.0], // 1
+    [   D,   -D,  0.0], // 2
+    [  -D,   -D,  0.0], // 3
+    [   D,  0.0,    D], // 4
+    [  -D,  0.0,    D], // 5
+    [   D,  0.0,   -D], // 6
+    [  -D,  0.0,   -D], // 7
+    [ 0.0,    D,    D], // 8
+    [ 0.0,   -D,    D], // 9
+    [ 0.0,    D,   -D], // 10
+    [ 0.0,   -D,   -D], // 11
+    [   D,    D,  0.0], // 12
+    [  -D,    D,  0.0], // 13
+    [ 0.0,   -D,    D], // 14
+    [ 0.0,   -D,   -D], // 15
+];
+
+/// The gradient directions used by `perlin_4d`, scaled to unit length.
+#[rustfmt::skip]
+const GRADIENTS_4D: [[f64; 4]; 32] = [
+    [   T,    T,    T,  0.0], // 0
+    [  -T,    T,    T,  0.0], // 1
+    [   T,   -T,    T,  0.0], // 2
+    [   T,    T,   -T,  0.0], // 3
+    [  -T,    T,   -T,  0.0], // 4
+    [   T,   -T,   -T,  0.0], // 5
+    [   T,   -T,   -T,  0.0], // 6
+    [   T,    T,  0.0,    T], // 7
+    [  -T,    T,  0.0,    T], // 8
+    [   T,   -T,  0.0,    T], // 9
+    [   T,    T,  0.0,   -T], // 10
+    [   T,    T,  0.0,   -T], // 11
+    [   T,    T,  0.0,   -T], // 12
+    [  -T,   -T,  0.0,   -T], // 13
+    [   T,  0.0,    T,    T], // 14
+    [  -T,  0.0,    T,    T], // 15
+    [   T,  0.0,   -T,    T], // 16
+    [   T,  0.0,    T,   -T], // 17
+    [   T,  0.0,    T,   -T], // 18
+    [   T,  0.0,    T,   -T], // 19
+    [  -T,  0.0,   -T,   -T], // 20
+    [ 0.0,    T,    T,    T], // 21
+    [ 0.0,   -T,    T,    T], // 22
+    [ 0.0,    T,   -T,    T], // 23
+    [ 0.0,    T,   -T,   -T], // 24
+    [ 0.0,   -T,   -T,   -T], // 25
+    [ 0.0,    T,   -T,   -T], // 26
+    [ 0.0,   -T,   -T,   -T], // 27
+    [   T,    T,    T,  0.0], // 28
+    [   T,    T,  0.0,    T], // 29
+    [   T,  0.0,    T,    T], // 30
+    [ 0.0,    T,    T,    T], // 31
+];
+
+/// Access to the gradient directions used by Perlin noise.
+pub trait PerlinGradients<const DIM: usize> {
+    /// Returns the unit-length gradient directions, indexed by the lattice hash
+    /// masked to the length of the table. Directions that appear more than
+    /// once are chosen proportionally more often.
+    fn gradients(&self) -> &'static [[f64; DIM]];
+}
+
+macro_rules! impl_perlin_gradients {
+    ($dim:expr, $table:ident) => {
+        impl PerlinGradients<$dim> for Perlin {
+            fn gradients(&self) -> &'static [[f64; $dim]] {
+                &$table
+            }
+        }
+    };
+}
+
+impl_perlin_gradients!(1, GRADIENTS_1D);
+impl_perlin_gradients!(2, GRADIENTS_2D);
+impl_perlin_gradients!(3, GRADIENTS_3D);
+impl_perlin_gradients!(4, GRADIENTS_4D);
+
 /// 1-dimensional perlin noise
 impl NoiseFn<f64, 1> for Perlin {
     fn get(&self, point: [f64; 1]) -> f64 {
-        perlin_1d(point[0], &self.perm_table)
+        let point = self.lattice_point(point);
+        match self.tile_period {
+            Some(period) => perlin_1d(point[0], &PeriodicHasher::new(&self.perm_table, period)),
+            None => perlin_1d(point[0], &self.perm_table),
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<1>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Perlin{{seed:{}}}", self.seed)
     }
 }
 
 /// 2-dimensional perlin noise
 impl NoiseFn<f64, 2> for Perlin {
     fn get(&self, point: [f64; 2]) -> f64 {
-        perlin_2d(point.into(), &self.perm_table)
+        let point = self.lattice_point(point);
+        match self.tile_period {
+            Some(period) => perlin_2d(point.into(), &PeriodicHasher::new(&self.perm_table, period)),
+            None => perlin_2d(point.into(), &self.perm_table),
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<2>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Perlin{{seed:{}}}", self.seed)
     }
 }
 
 /// 3-dimensional perlin noise
 impl NoiseFn<f64, 3> for Perlin {
     fn get(&self, point: [f64; 3]) -> f64 {
-        perlin_3d(point.into(), &self.perm_table)
+        let point = self.lattice_point(point);
+        match self.tile_period {
+            Some(period) => perlin_3d(point.into(), &PeriodicHasher::new(&self.perm_table, period)),
+            None => perlin_3d(point.into(), &self.perm_table),
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<3>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Perlin{{seed:{}}}", self.seed)
     }
 }
 
 /// 4-dimensional perlin noise
 impl NoiseFn<f64, 4> for Perlin {
     fn get(&self, point: [f64; 4]) -> f64 {
-        perlin_4d(point.into(), &self.perm_table)
+        let point = self.lattice_point(point);
+        match self.tile_period {
+            Some(period) => perlin_4d(point.into(), &PeriodicHasher::new(&self.perm_table, period)),
+            None => perlin_4d(point.into(), &self.perm_table),
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<4>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Perlin{{seed:{}}}", self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::NoiseHasher;
+
+    /// Hasher that selects the same gradient at every lattice point.
+    struct FixedHasher(usize);
+
+    impl NoiseHasher for FixedHasher {
+        fn hash(&self, _to_hash: &[isize]) -> usize {
+            self.0
+        }
+    }
+
+    fn assert_unit_length<const DIM: usize>(gradients: &[[f64; DIM]]) {
+        for gradient in gradients {
+            let length = gradient.iter().map(|c| c * c).sum::<f64>().sqrt();
+            assert!((length - 1.0).abs() < 1e-12, "{:?}", gradient);
+        }
+    }
+
+    #[test]
+    fn gradients_are_unit_length() {
+        let perlin = Perlin::new(0);
+
+        assert_unit_length(PerlinGradients::<1>::gradients(&perlin));
+        assert_unit_length(PerlinGradients::<2>::gradients(&perlin));
+        assert_unit_length(PerlinGradients::<3>::gradients(&perlin));
+        assert_unit_length(PerlinGradients::<4>::gradients(&perlin));
+    }
+
+    #[test]
+    fn gradients_match_evaluated_slopes() {
+        const STEP: f64 = 1e-3;
+        let perlin = Perlin::new(0);
+
+        // Near a lattice point that uses the gradient `g`, the noise is close
+        // to a scaled `g · p`, so the slope along each axis points along `g`.
+        for (index, gradient) in PerlinGradients::<3>::gradients(&perlin).iter().enumerate() {
+            let hasher = FixedHasher(index);
+            let slope = [
+                perlin_3d([STEP, 0.0, 0.0].into(), &hasher),
+                perlin_3d([0.0, STEP, 0.0].into(), &hasher),
+                perlin_3d([0.0, 0.0, STEP].into(), &hasher),
+            ];
+            let length = slope.iter().map(|c| c * c).sum::<f64>().sqrt();
+
+            for (slope, expected) in slope.iter().zip(gradient) {
+                assert!(
+                    (slope / length - expected).abs() < 1e-4,
+                    "gradient {}",
+                    index
+                );
+            }
+        }
+
+        for (index, gradient) in PerlinGradients::<4>::gradients(&perlin).iter().enumerate() {
+            let hasher = FixedHasher(index);
+            let slope = [
+                perlin_4d([STEP, 0.0, 0.0, 0.0].into(), &hasher),
+                perlin_4d([0.0, STEP, 0.0, 0.0].into(), &hasher),
+                perlin_4d([0.0, 0.0, STEP, 0.0].into(), &hasher),
+                perlin_4d([0.0, 0.0, 0.0, STEP].into(), &hasher),
+            ];
+            let length = slope.iter().map(|c| c * c).sum::<f64>().sqrt();
+
+            for (slope, expected) in slope.iter().zip(gradient) {
+                assert!(
+                    (slope / length - expected).abs() < 1e-4,
+                    "gradient {}",
+                    index
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tileable_wraps_for_any_period() {
+        let perlin = Perlin::new(9).set_tileable(37);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.913 - 20.0, i as f64 * -0.571 + 3.0];
+            let value = perlin.get(point);
+
+            assert!((value - perlin.get([point[0] + 37.0, point[1]])).abs() < 1e-9);
+            assert!((value - perlin.get([point[0], point[1] - 37.0])).abs() < 1e-9);
+            assert!((value - perlin.get([point[0] + 74.0, point[1] + 37.0])).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn tileable_edge_cases() {
+        let untiled = Perlin::new(9);
+        let single = untiled.set_tileable(1);
+        let large = untiled.set_tileable(1 << 20);
+
+        for i in 0..50 {
+            // Every cell shares the same gradients with a period of 1.
+            let point = [i as f64 * 0.037, i as f64 * 0.021];
+            let value = single.get(point);
+            assert!((value - single.get([point[0] + 5.0, point[1] + 2.0])).abs() < 1e-9);
+
+            // A period beyond the sampled region leaves the noise unchanged.
+            let point = [i as f64 * 13.7, i as f64 * 7.3];
+            assert_eq!(untiled.get(point), large.get(point));
+        }
+    }
+
+    #[test]
+    fn lattice_offset_shifts_field() {
+        let perlin = Perlin::new(9);
+        let offset = [0.37, -2.71, 5.5];
+        let shifted = perlin.set_lattice_offset(offset);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311, i as f64 * 0.05];
+            let moved = [
+                point[0] + offset[0],
+                point[1] + offset[1],
+                point[2] + offset[2],
+            ];
+            assert!((perlin.get(point) - shifted.get(moved)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn lattice_offsets_decorrelate_layers() {
+        let first = Perlin::new(9).set_lattice_offset([0.25, 0.5]);
+        let second = Perlin::new(9).set_lattice_offset([0.75, 0.125]);
+
+        // Pearson correlation of the two layers over a grid of points.
+        let (mut sum_a, mut sum_b, mut sum_ab, mut sum_aa, mut sum_bb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let count = 2500;
+        for i in 0..count {
+            let point = [(i % 50) as f64 * 0.37, (i / 50) as f64 * 0.37];
+            let (a, b) = (first.get(point), second.get(point));
+            sum_a += a;
+            sum_b += b;
+            sum_ab += a * b;
+            sum_aa += a * a;
+            sum_bb += b * b;
+        }
+        let count = count as f64;
+        let covariance = sum_ab / count - sum_a * sum_b / (count * count);
+        let variance_a = sum_aa / count - sum_a * sum_a / (count * count);
+        let variance_b = sum_bb / count - sum_b * sum_b / (count * count);
+        let correlation: f64 = covariance / (variance_a * variance_b).sqrt();
+
+        assert!(correlation.abs() < 0.1, "correlation {}", correlation);
+    }
+
+    #[test]
+    fn axis_frequency_scales_each_axis() {
+        let perlin = Perlin::new(9);
+        let stretched = perlin.set_frequency_per_axis([2.0, 0.5, 1.0]);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311, i as f64 * 0.05];
+            let scaled = [point[0] * 2.0, point[1] * 0.5, point[2]];
+            assert_eq!(perlin.get(scaled), stretched.get(point));
+        }
+    }
+
+    #[test]
+    fn lattice_offset_is_independent_of_frequency() {
+        let stretched = Perlin::new(9).set_frequency_per_axis([2.0, 0.5, 3.0]);
+        let offset = [0.37, -2.71, 5.5];
+        let shifted = stretched.set_lattice_offset(offset);
+
+        // The offset moves the field by the same distance in input space,
+        // whatever the frequency.
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311, i as f64 * 0.05];
+            let moved = [
+                point[0] + offset[0],
+                point[1] + offset[1],
+                point[2] + offset[2],
+            ];
+            assert!((stretched.get(point) - shifted.get(moved)).abs() < 1e-12);
+        }
     }
 }
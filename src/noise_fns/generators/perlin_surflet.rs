@@ -3,6 +3,7 @@ use crate::{
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
+use alloc::{format, string::String};
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
 ///
@@ -56,6 +57,14 @@ impl NoiseFn<f64, 2> for PerlinSurflet {
     fn get(&self, point: [f64; 2]) -> f64 {
         perlin_surflet_2d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("PerlinSurflet{{seed:{}}}", self.seed)
+    }
 }
 
 /// 3-dimensional perlin noise
@@ -63,6 +72,14 @@ impl NoiseFn<f64, 3> for PerlinSurflet {
     fn get(&self, point: [f64; 3]) -> f64 {
         perlin_surflet_3d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("PerlinSurflet{{seed:{}}}", self.seed)
+    }
 }
 
 /// 4-dimensional perlin noise
@@ -70,4 +87,12 @@ impl NoiseFn<f64, 4> for PerlinSurflet {
     fn get(&self, point: [f64; 4]) -> f64 {
         perlin_surflet_4d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("PerlinSurflet{{seed:{}}}", self.seed)
+    }
 }
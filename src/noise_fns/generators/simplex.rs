@@ -1,8 +1,10 @@
 use crate::{
     core::simplex::*,
+    math::vectors::{Vector3, Vector4},
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
+use alloc::{format, string::String};
 
 /// Noise function that outputs N-dimensional Simplex noise.
 ///
@@ -10,15 +12,80 @@ use crate::{
 pub struct Simplex {
     seed: u32,
     hasher: PermutationTable,
+    #[serde(default)]
+    lattice_rotation: bool,
+    #[serde(default)]
+    normalization: bool,
 }
 
 impl Simplex {
     pub const DEFAULT_SEED: u32 = 0;
 
+    /// Factor applied to 2-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±0.989.
+    pub const SCALE_FACTOR_2D: f64 = 1.0107;
+
+    /// Factor applied to 3-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±0.809.
+    pub const SCALE_FACTOR_3D: f64 = 1.2365;
+
+    /// Factor applied to 4-dimensional output when normalization is enabled.
+    /// The unscaled extremes are about ±0.76.
+    pub const SCALE_FACTOR_4D: f64 = 1.3;
+
     pub fn new(seed: u32) -> Self {
         Simplex {
             seed,
             hasher: PermutationTable::new(seed),
+            lattice_rotation: false,
+            normalization: false,
+        }
+    }
+
+    /// Enables or disables rotating the simplex lattice away from the world
+    /// axes before sampling.
+    ///
+    /// With the rotation enabled, the _x_, _y_ and _z_ coordinates of 3- and
+    /// 4-dimensional input points are rotated so that the lattice no longer
+    /// lines up with the world axes, which reduces the directional streaks
+    /// visible in planar slices. 2-dimensional noise is unaffected, since its
+    /// lattice is already not aligned with the axes. Disabled by default, to
+    /// preserve the original output.
+    pub fn with_lattice_rotation(self, lattice_rotation: bool) -> Self {
+        Self {
+            lattice_rotation,
+            ..self
+        }
+    }
+
+    pub fn lattice_rotation(&self) -> bool {
+        self.lattice_rotation
+    }
+
+    /// Enables or disables scaling the output to fill the range `[-1, 1]`.
+    ///
+    /// The raw output of Simplex noise never reaches ±1, and its extremes
+    /// shrink as the dimension grows. With the normalization enabled, the
+    /// output is multiplied by [`SCALE_FACTOR_2D`](Self::SCALE_FACTOR_2D),
+    /// [`SCALE_FACTOR_3D`](Self::SCALE_FACTOR_3D) or
+    /// [`SCALE_FACTOR_4D`](Self::SCALE_FACTOR_4D) and clamped to `[-1, 1]`.
+    /// Disabled by default, to preserve the original output.
+    pub fn with_normalization(self, normalization: bool) -> Self {
+        Self {
+            normalization,
+            ..self
+        }
+    }
+
+    pub fn normalization(&self) -> bool {
+        self.normalization
+    }
+
+    fn normalize(&self, value: f64, scale_factor: f64) -> f64 {
+        if self.normalization {
+            (value * scale_factor).clamp(-1.0, 1.0)
+        } else {
+            value
         }
     }
 }
@@ -41,6 +108,7 @@ impl Seedable for Simplex {
         Simplex {
             seed,
             hasher: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -54,24 +122,147 @@ impl NoiseFn<f64, 2> for Simplex {
     fn get(&self, point: [f64; 2]) -> f64 {
         let (result, _) = simplex_2d(point.into(), &self.hasher);
 
-        result
+        self.normalize(result, Self::SCALE_FACTOR_2D)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("Simplex{{seed:{}}}", self.seed)
     }
 }
 
 /// 3-dimensional Simplex noise
 impl NoiseFn<f64, 3> for Simplex {
     fn get(&self, point: [f64; 3]) -> f64 {
-        let (result, _) = simplex_3d(point.into(), &self.hasher);
+        let mut point = Vector3::from(point);
+        if self.lattice_rotation {
+            point = rotate_lattice_3d(point);
+        }
 
-        result
+        let (result, _) = simplex_3d(point, &self.hasher);
+
+        self.normalize(result, Self::SCALE_FACTOR_3D)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("Simplex{{seed:{}}}", self.seed)
     }
 }
 
 /// 4-dimensional Simplex noise
 impl NoiseFn<f64, 4> for Simplex {
     fn get(&self, point: [f64; 4]) -> f64 {
-        let (result, _) = simplex_4d(point.into(), &self.hasher);
+        let mut point = Vector4::from(point);
+        if self.lattice_rotation {
+            let xyz = rotate_lattice_3d(Vector3::new(point.x, point.y, point.z));
+            point = Vector4::new(xyz.x, xyz.y, xyz.z, point.w);
+        }
+
+        let (result, _) = simplex_4d(point, &self.hasher);
+
+        self.normalize(result, Self::SCALE_FACTOR_4D)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("Simplex{{seed:{}}}", self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Strength of the four-fold (axis-aligned) harmonic of the gradient
+    /// direction distribution in the slice `z = 0.37`, relative to the total
+    /// gradient power. An isotropic slice returns a value close to 0.0.
+    fn axis_alignment(simplex: &Simplex) -> f64 {
+        const STEP: f64 = 0.01;
+
+        let mut total = 0.0;
+        let mut four_fold = (0.0, 0.0);
+        for i in 0..300 {
+            for j in 0..300 {
+                let x = i as f64 * 0.173;
+                let y = j as f64 * 0.191;
+
+                let gx = simplex.get([x + STEP, y, 0.37]) - simplex.get([x - STEP, y, 0.37]);
+                let gy = simplex.get([x, y + STEP, 0.37]) - simplex.get([x, y - STEP, 0.37]);
+                let power = gx * gx + gy * gy;
+                let angle = 4.0 * gy.atan2(gx);
+
+                total += power;
+                four_fold.0 += power * angle.cos();
+                four_fold.1 += power * angle.sin();
+            }
+        }
+
+        (four_fold.0 * four_fold.0 + four_fold.1 * four_fold.1).sqrt() / total
+    }
+
+    #[test]
+    fn lattice_rotation_lowers_axis_alignment() {
+        let simplex = Simplex::new(0);
+
+        let aligned = axis_alignment(&simplex);
+        let rotated = axis_alignment(&simplex.with_lattice_rotation(true));
+
+        assert!(
+            rotated * 2.0 < aligned,
+            "aligned: {}, rotated: {}",
+            aligned,
+            rotated
+        );
+    }
+
+    #[test]
+    fn normalization_fills_output_range() {
+        let simplex = Simplex::new(0).with_normalization(true);
+
+        let (mut min_2d, mut max_2d) = (0.0_f64, 0.0_f64);
+        let (mut min_3d, mut max_3d) = (0.0_f64, 0.0_f64);
+        for i in 0..400 {
+            for j in 0..400 {
+                let (x, y) = (i as f64 * 0.137, j as f64 * 0.149);
+
+                let value = simplex.get([x, y]);
+                min_2d = min_2d.min(value);
+                max_2d = max_2d.max(value);
+
+                let value = simplex.get([x, y, 0.5 * x - 0.3 * y]);
+                min_3d = min_3d.min(value);
+                max_3d = max_3d.max(value);
+            }
+        }
+
+        for &extreme in &[-min_2d, max_2d, -min_3d, max_3d] {
+            assert!(extreme > 0.9 && extreme <= 1.0, "extreme: {}", extreme);
+        }
+    }
+
+    #[test]
+    fn lattice_rotation_is_off_by_default() {
+        let simplex = Simplex::new(0);
+        let point = [0.3, 1.7, -2.1];
 
-        result
+        assert!(!simplex.lattice_rotation());
+        assert_eq!(
+            simplex.get(point),
+            simplex.with_lattice_rotation(false).get(point)
+        );
+        assert_ne!(
+            simplex.get(point),
+            simplex.with_lattice_rotation(true).get(point)
+        );
     }
 }
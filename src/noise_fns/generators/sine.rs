@@ -0,0 +1,114 @@
+use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
+use core::f64::consts::TAU;
+
+/// Noise function that outputs a sine wave travelling along a direction.
+///
+/// The output is `sin(2π * frequency * (point · direction))`, which gives
+/// parallel bands perpendicular to the direction. This is useful as a
+/// deterministic periodic base to combine with noise, for ripples, waves and
+/// banding.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Sine {
+    /// Number of cycles per unit length along the direction.
+    pub frequency: f64,
+
+    /// Unit vector along which the wave travels. Axes beyond the dimension of
+    /// the input value are ignored. The default direction is the _x_ axis.
+    pub direction: [f64; 4],
+}
+
+impl Sine {
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            frequency: Self::DEFAULT_FREQUENCY,
+            direction: [1.0, 0.0, 0.0, 0.0],
+        }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the direction along which the wave travels. The direction is
+    /// normalized, so only its orientation matters. Axes that are not given
+    /// are set to zero.
+    pub fn set_direction<const DIM: usize>(self, direction: [f64; DIM]) -> Self {
+        assert!(DIM <= 4, "Sine supports at most four dimensions");
+
+        let length = direction.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+        let mut normalized = [0.0; 4];
+        if length > 0.0 {
+            for (normalized, value) in normalized.iter_mut().zip(direction.iter()) {
+                *normalized = value / length;
+            }
+        }
+
+        Self {
+            direction: normalized,
+            ..self
+        }
+    }
+}
+
+impl Default for Sine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DIM: usize> NoiseFn<f64, DIM> for Sine {
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let distance: f64 = point
+            .iter()
+            .zip(self.direction.iter())
+            .map(|(value, direction)| value * direction)
+            .sum();
+
+        (TAU * self.frequency * distance).sin()
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(self.frequency.abs())
+    }
+
+    fn describe(&self) -> String {
+        format!("Sine{{frequency:{}}}", self.frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wave_repeats_every_wavelength() {
+        let sine = Sine::new().set_frequency(2.5).set_direction([3.0, 4.0]);
+        // A step of one wavelength along the direction.
+        let wavelength = 1.0 / 2.5;
+        let step = [0.6 * wavelength, 0.8 * wavelength];
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * -0.311 + 3.0];
+            let next = [point[0] + step[0], point[1] + step[1]];
+            assert!((sine.get(point) - sine.get(next)).abs() < 1e-9);
+
+            // Moving half a wavelength inverts the wave.
+            let half = [point[0] + step[0] / 2.0, point[1] + step[1] / 2.0];
+            assert!((sine.get(point) + sine.get(half)).abs() < 1e-9);
+
+            // Moving perpendicular to the direction leaves it unchanged.
+            let across = [point[0] + 0.8, point[1] - 0.6];
+            assert!((sine.get(point) - sine.get(across)).abs() < 1e-9);
+        }
+
+        assert!((sine.get([0.6 * wavelength / 4.0, 0.8 * wavelength / 4.0]) - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    noise_fns::{NoiseFn, Seedable},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+use alloc::{format, string::String};
+
+/// Noise function that outputs rare, isolated impulses on a zero background.
+///
+/// The input space is divided into cells of size `1 / frequency`. Each cell
+/// contains an impulse with probability `density`, decided by hashing the
+/// integer coordinates of the cell. An impulse sits at a pseudo-random position
+/// within its cell, and its output falls off smoothly from 1.0 at its center to
+/// 0.0 at `radius` (in cells). Everywhere else the output is 0.0.
+///
+/// This suits star fields and sparse scattering, where most of the output
+/// should be empty. Unlike [`Worley`](crate::Worley), which places a feature
+/// point in every cell, the impulses are independent and most cells are
+/// empty.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Sparse {
+    /// Number of cells per unit length. The default is 1.0.
+    pub frequency: f64,
+
+    /// Fraction of the cells that contain an impulse, between 0.0 and 1.0. The
+    /// default is 0.05.
+    pub density: f64,
+
+    /// Radius of each impulse in cells, between 0.0 and 0.5. The default is
+    /// 0.25.
+    pub radius: f64,
+
+    seed: u32,
+    perm_table: PermutationTable,
+}
+
+impl Sparse {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_DENSITY: f64 = 0.05;
+    pub const DEFAULT_RADIUS: f64 = 0.25;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            frequency: Self::DEFAULT_FREQUENCY,
+            density: Self::DEFAULT_DENSITY,
+            radius: Self::DEFAULT_RADIUS,
+            seed,
+            perm_table: PermutationTable::new(seed),
+        }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the fraction of the cells that contain an impulse. The density is
+    /// clamped to between 0.0 and 1.0, and is resolved in steps of 1/256.
+    pub fn set_density(self, density: f64) -> Self {
+        Self {
+            density: density.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Sets the radius of each impulse in cells. The radius is clamped to
+    /// between 0.0 and 0.5, so that every impulse fits within its cell.
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self {
+            radius: radius.clamp(0.0, 0.5),
+            ..self
+        }
+    }
+
+    /// Returns the output at `point`, which has at most four dimensions since
+    /// the key hashed for each cell holds the cell on every axis plus one
+    /// selector entry.
+    fn impulse<const DIM: usize>(&self, point: [f64; DIM]) -> f64 {
+        if self.radius <= 0.0 {
+            return 0.0;
+        }
+
+        // The first entry of the key selects between the hash deciding whether
+        // the cell has an impulse and the hashes of its position on each axis.
+        let mut key = [0; 5];
+        let mut position = [0.0; DIM];
+        for ((key, position), &value) in key[1..].iter_mut().zip(&mut position).zip(&point) {
+            let value = value * self.frequency;
+            let cell = value.floor();
+            *key = cell as isize;
+            *position = value - cell;
+        }
+
+        let presence = self.perm_table.hash(&key[1..=DIM]) as f64;
+        if presence >= self.density * 256.0 {
+            return 0.0;
+        }
+
+        // Keep the impulse far enough from the edges of the cell that it never
+        // reaches into the neighboring cells.
+        let mut distance_squared = 0.0;
+        for (axis, position) in position.iter().enumerate() {
+            key[0] = axis as isize + 1;
+            let center = self.radius
+                + self.perm_table.hash(&key[..=DIM]) as f64 / 255.0 * (1.0 - 2.0 * self.radius);
+            distance_squared += (position - center) * (position - center);
+        }
+
+        let falloff = 1.0 - distance_squared / (self.radius * self.radius);
+        if falloff <= 0.0 {
+            0.0
+        } else {
+            falloff * falloff * falloff
+        }
+    }
+}
+
+impl Default for Sparse {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for Sparse {
+    fn set_seed(self, seed: u32) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            perm_table: PermutationTable::new(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+macro_rules! impl_sparse {
+    ($dim:expr) => {
+        impl NoiseFn<f64, $dim> for Sparse {
+            fn get(&self, point: [f64; $dim]) -> f64 {
+                self.impulse(point)
+            }
+
+            fn output_range(&self) -> Option<(f64, f64)> {
+                Some((0.0, 1.0))
+            }
+
+            fn dominant_frequency(&self) -> Option<f64> {
+                Some(self.frequency.abs())
+            }
+
+            fn describe(&self) -> String {
+                format!(
+                    "Sparse{{seed:{}, frequency:{}, density:{}}}",
+                    self.seed, self.frequency, self.density
+                )
+            }
+        }
+    };
+}
+
+impl_sparse!(1);
+impl_sparse!(2);
+impl_sparse!(3);
+impl_sparse!(4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lit_cells_match_density() {
+        const CELLS: usize = 64;
+        const SAMPLES: usize = 8;
+
+        for &density in &[0.02, 0.1, 0.3] {
+            let sparse = Sparse::new(4).set_frequency(0.5).set_density(density);
+
+            // The samples are close enough together that every impulse has a
+            // sample within its bright core.
+            let mut lit = 0;
+            for cell_y in 0..CELLS {
+                for cell_x in 0..CELLS {
+                    let mut brightest: f64 = 0.0;
+                    for y in 0..SAMPLES {
+                        for x in 0..SAMPLES {
+                            let point = [
+                                (cell_x as f64 + (x as f64 + 0.5) / SAMPLES as f64) * 2.0,
+                                (cell_y as f64 + (y as f64 + 0.5) / SAMPLES as f64) * 2.0,
+                            ];
+                            brightest = brightest.max(sparse.get(point));
+                        }
+                    }
+
+                    if brightest > 0.5 {
+                        lit += 1;
+                    }
+                }
+            }
+
+            let fraction = lit as f64 / (CELLS * CELLS) as f64;
+            assert!(
+                (fraction - density).abs() < 0.02,
+                "{} != {}",
+                fraction,
+                density
+            );
+        }
+    }
+
+    #[test]
+    fn empty_cells_output_zero() {
+        let sparse = Sparse::new(4).set_density(0.0);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311, i as f64 * 0.05];
+            assert_eq!(0.0, sparse.get(point));
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
+use alloc::{format, string::String};
 
 /// Noise function that outputs 2/3-dimensional Super Simplex noise.
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
@@ -53,6 +54,14 @@ impl NoiseFn<f64, 2> for SuperSimplex {
     fn get(&self, point: [f64; 2]) -> f64 {
         super_simplex_2d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("SuperSimplex{{seed:{}}}", self.seed)
+    }
 }
 
 /// 3-dimensional Super Simplex noise
@@ -60,4 +69,12 @@ impl NoiseFn<f64, 3> for SuperSimplex {
     fn get(&self, point: [f64; 3]) -> f64 {
         super_simplex_3d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn describe(&self) -> String {
+        format!("SuperSimplex{{seed:{}}}", self.seed)
+    }
 }
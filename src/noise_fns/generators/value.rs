@@ -1,12 +1,19 @@
 use crate::{
-    core::value::{value_2d, value_3d, value_4d},
+    core::value::{value_1d, value_2d, value_3d, value_4d},
     noise_fns::{NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
+use alloc::{format, string::String};
 
-/// Noise function that outputs 2/3/4-dimensional Value noise.
+/// Noise function that outputs 1/2/3/4-dimensional Value noise.
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Value {
+    /// Number of lattice cells per unit length along each axis. Axes beyond
+    /// the dimension of the input value are ignored. The default is 1.0 on
+    /// every axis.
+    #[serde(default = "super::default_axis_frequency")]
+    pub axis_frequency: [f64; 4],
+
     seed: u32,
     perm_table: PermutationTable,
 }
@@ -16,10 +23,21 @@ impl Value {
 
     pub fn new(seed: u32) -> Self {
         Self {
+            axis_frequency: super::default_axis_frequency(),
             seed,
             perm_table: PermutationTable::new(seed),
         }
     }
+
+    /// Sets the number of lattice cells per unit length along each axis, which
+    /// stretches the noise independently along each axis. Axes that are not
+    /// given keep a frequency of 1.0.
+    pub fn set_frequency_per_axis<const DIM: usize>(self, frequency: [f64; DIM]) -> Self {
+        Self {
+            axis_frequency: super::pad_axis_frequency(frequency),
+            ..self
+        }
+    }
 }
 
 impl Default for Value {
@@ -40,6 +58,7 @@ impl Seedable for Value {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -48,23 +67,82 @@ impl Seedable for Value {
     }
 }
 
+/// 1-dimensional value noise
+impl NoiseFn<f64, 1> for Value {
+    fn get(&self, point: [f64; 1]) -> f64 {
+        let point = super::scale_axes(point, &self.axis_frequency);
+        value_1d(point[0], &self.perm_table)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<1>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Value{{seed:{}}}", self.seed)
+    }
+}
+
 /// 2-dimensional value noise
 impl NoiseFn<f64, 2> for Value {
     fn get(&self, point: [f64; 2]) -> f64 {
+        let point = super::scale_axes(point, &self.axis_frequency);
         value_2d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<2>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Value{{seed:{}}}", self.seed)
+    }
 }
 
 /// 3-dimensional value noise
 impl NoiseFn<f64, 3> for Value {
     fn get(&self, point: [f64; 3]) -> f64 {
+        let point = super::scale_axes(point, &self.axis_frequency);
         value_3d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<3>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Value{{seed:{}}}", self.seed)
+    }
 }
 
 /// 4-dimensional value noise
 impl NoiseFn<f64, 4> for Value {
     fn get(&self, point: [f64; 4]) -> f64 {
+        let point = super::scale_axes(point, &self.axis_frequency);
         value_4d(point.into(), &self.perm_table)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        Some(super::max_axis_frequency::<4>(&self.axis_frequency))
+    }
+
+    fn describe(&self) -> String {
+        format!("Value{{seed:{}}}", self.seed)
+    }
 }
@@ -2,17 +2,22 @@ use crate::{
     core::worley::*,
     math::vectors::*,
     noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    permutationtable::{NoiseHasher, PeriodicHasher, PermutationTable},
+    utils::Bounds,
 };
-use alloc::rc::Rc;
+use alloc::{format, string::String, sync::Arc};
 
 /// Noise function that outputs Worley noise.
+///
+/// The output depends only on the input value, so a world built in separately
+/// evaluated chunks, possibly on different threads, agrees exactly along the
+/// chunk borders.
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Worley {
     /// Specifies the distance function to use when calculating the boundaries of
     /// the cell.
     #[serde(skip, default = "worley_default_distance_fn")]
-    pub distance_function: Rc<DistanceFunction>,
+    pub distance_function: Arc<DistanceFunction>,
 
     /// Signifies whether the distance from the borders of the cell should be returned, or the
     /// value for the cell.
@@ -21,23 +26,53 @@ pub struct Worley {
     /// Frequency of the seed points.
     pub frequency: f64,
 
+    /// Number of cells after which the cell pattern repeats along each axis,
+    /// if tiling is enabled.
+    pub tile_period: Option<usize>,
+
+    /// Width of the smooth minimum used to blend the distances to nearby
+    /// feature points, or 0.0 for the hard nearest distance.
+    #[serde(default)]
+    pub smoothing: f64,
+
+    /// Factor by which the cells are stretched along each axis. Axes beyond
+    /// the dimension of the input value are ignored.
+    #[serde(default = "worley_default_cell_scale")]
+    pub cell_scale: [f64; 4],
+
+    /// Number of cells searched on either side of the input value's cell by
+    /// `get_k_nearest` and the smoothed distance.
+    #[serde(default = "worley_default_search_radius")]
+    pub search_radius: usize,
+
+    /// Whether the distance to each feature point is divided by a
+    /// pseudo-random weight of its cell, which makes some cells grow larger
+    /// than others.
+    #[serde(default)]
+    pub weighted: bool,
+
     seed: u32,
     perm_table: PermutationTable,
 }
 
-impl std::fmt::Debug for Worley {
+impl core::fmt::Debug for Worley {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Worley")
             .field("distance_function", &"dyn Fn(&[f64], &[f64]) -> f64")
             .field("return_type", &self.return_type)
             .field("frequency", &self.frequency)
+            .field("tile_period", &self.tile_period)
+            .field("smoothing", &self.smoothing)
+            .field("cell_scale", &self.cell_scale)
+            .field("search_radius", &self.search_radius)
+            .field("weighted", &self.weighted)
             .field("seed", &self.seed)
             .field("perm_table", &self.perm_table)
             .finish()
     }
 }
 
-type DistanceFunction = dyn Fn(&[f64], &[f64]) -> f64;
+type DistanceFunction = dyn Fn(&[f64], &[f64]) -> f64 + Send + Sync;
 
 impl Worley {
     pub const DEFAULT_SEED: u32 = 0;
@@ -50,16 +85,26 @@ impl Worley {
             distance_function: worley_default_distance_fn(),
             return_type: ReturnType::Value,
             frequency: Self::DEFAULT_FREQUENCY,
+            tile_period: None,
+            smoothing: 0.0,
+            cell_scale: worley_default_cell_scale(),
+            search_radius: Self::K_NEAREST_SEARCH_RADIUS,
+            weighted: false,
         }
     }
 
     /// Sets the distance function used by the Worley cells.
+    ///
+    /// The functions in `distance_functions` cover the common metrics. When
+    /// distances are only thresholded or compared, prefer
+    /// `distance_functions::euclidean_squared` over `euclidean`, as it avoids a
+    /// square root for every candidate feature point.
     pub fn set_distance_function<F>(self, function: F) -> Self
     where
-        F: Fn(&[f64], &[f64]) -> f64 + 'static,
+        F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static,
     {
         Self {
-            distance_function: Rc::new(function),
+            distance_function: Arc::new(function),
             ..self
         }
     }
@@ -77,10 +122,368 @@ impl Worley {
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self { frequency, ..self }
     }
+
+    /// Multiplies the frequency by a separate factor along each axis, which
+    /// stretches the whole pattern independently along each axis. Axes that
+    /// are not given keep a factor of 1.0.
+    ///
+    /// This sets a cell scale of `1 / frequency` along each axis, see
+    /// `set_cell_scale`, so setting every factor to `f` with the default
+    /// frequency of 1.0 matches `set_frequency(f)`.
+    pub fn set_frequency_per_axis<const DIM: usize>(self, frequency: [f64; DIM]) -> Self {
+        self.set_cell_scale(frequency.map(|frequency| 1.0 / frequency))
+    }
+
+    /// Makes the output tile seamlessly every `period` cells along each axis,
+    /// by wrapping the cell-neighborhood search modulo the period. Feature
+    /// points near one edge of the tile therefore also appear at the opposite
+    /// edge.
+    ///
+    /// The period is measured in cells, so the output repeats every
+    /// `period / frequency` units of input space. A period of zero disables
+    /// tiling.
+    pub fn set_tileable(self, period: usize) -> Self {
+        Self {
+            tile_period: if period > 0 { Some(period) } else { None },
+            ..self
+        }
+    }
+
+    /// Replaces the distance to the nearest feature point with a smooth minimum
+    /// of the distances to the feature points around the input value, which
+    /// rounds off the creases along the cell borders and makes neighbouring
+    /// cells merge into each other.
+    ///
+    /// The smoothing is the width of the blend, in cell space. Values around
+    /// 0.05 give slightly rounded cells, while values around 0.2 produce soft,
+    /// blobby shapes. The smooth minimum is always lower than the nearest
+    /// distance, so the output shifts downwards as the smoothing increases. A
+    /// smoothing of zero restores the standard output.
+    ///
+    /// This only affects `ReturnType::Distance`. The smooth minimum visits
+    /// every cell within the search radius of the input value, which is
+    /// considerably slower than the standard search.
+    pub fn set_smoothing(self, smoothing: f64) -> Self {
+        Self { smoothing, ..self }
+    }
+
+    /// Enables or disables weighting the cells, which gives irregular cells of
+    /// uneven sizes, as in a multiplicatively weighted Voronoi diagram.
+    ///
+    /// Each cell receives a pseudo-random weight between 0.5 and 1.0, and the
+    /// distance to its feature point is divided by that weight before the
+    /// nearest feature point is chosen. Cells with a large weight therefore
+    /// claim space from their lighter neighbours, and the borders between
+    /// cells become curved. `ReturnType::Distance` outputs the weighted
+    /// distance scaled by 0.5, which keeps it within the range of the
+    /// unweighted distance, and `get_cell_point`, `get_nearest_offset` and
+    /// `get_distance_and_value` report the weighted owner of the input value.
+    ///
+    /// Like the smoothed distance, the weighted search visits every cell
+    /// within the search radius of the input value, which is considerably
+    /// slower than the standard search. Smoothing, when enabled, takes
+    /// precedence and uses the unweighted distances, as does `get_k_nearest`.
+    pub fn set_weighted(self, weighted: bool) -> Self {
+        Self { weighted, ..self }
+    }
+
+    /// Stretches the cells by the given factor along each axis, which produces
+    /// elongated cells for grain and other directional textures. A scale of
+    /// `[2.0, 1.0]` makes the cells roughly twice as long along the _x_ axis
+    /// as along the _y_ axis.
+    ///
+    /// The input value is divided by the scale before the cell search, which
+    /// gives the same output as wrapping the noise function in a `ScalePoint`
+    /// with the reciprocal factors. Unlike the wrapper, the cell queries such
+    /// as `get_cell_point` and `get_nearest_offset` remain available and
+    /// report positions in input space. Axes that are not given keep a scale
+    /// of 1.0.
+    pub fn set_cell_scale<const DIM: usize>(self, scale: [f64; DIM]) -> Self {
+        assert!(DIM <= 4, "Worley supports at most four dimensions");
+
+        let mut cell_scale = worley_default_cell_scale();
+        cell_scale[..DIM].copy_from_slice(&scale);
+
+        Self { cell_scale, ..self }
+    }
+
+    /// Sets the frequency and tiling so that exactly `cells_per_axis` cells span
+    /// the region covered by `region_bounds` along each axis.
+    ///
+    /// The cell pattern then repeats every region, so a map built over the
+    /// region contains exactly `cells_per_axis` cells along each axis,
+    /// regardless of its resolution. Since [`Bounds`] only holds non-empty
+    /// ranges, the frequency is always positive and finite. A count of zero
+    /// disables tiling and leaves the frequency unchanged.
+    pub fn for_grid(self, cells_per_axis: usize, region_bounds: Bounds) -> Self {
+        if cells_per_axis == 0 {
+            return self.set_tileable(0);
+        }
+
+        self.set_frequency(cells_per_axis as f64 / region_bounds.extent())
+            .set_tileable(cells_per_axis)
+    }
+
+    /// Returns the position of the feature point of the cell that owns the
+    /// input value.
+    ///
+    /// Every input value within a cell returns exactly the same position, which
+    /// makes this suitable for placing one object per cell at a stable location.
+    /// The position is given in the same coordinate space as the input value.
+    pub fn get_cell_point<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM]
+    where
+        Self: WorleyFeatures<DIM>,
+    {
+        let mut feature_point = self.nearest_feature(point).point;
+        for (value, frequency) in feature_point.iter_mut().zip(self.cell_frequency()) {
+            *value /= frequency;
+        }
+
+        feature_point
+    }
+
+    /// Returns the vector from the input value to the feature point of the
+    /// cell that owns it.
+    ///
+    /// The vector points towards the center of the cell and shrinks to zero at
+    /// the feature point, which suits flow fields that pull towards the cells.
+    /// It is given in the same coordinate space as the input value, so with the
+    /// Euclidean distance function and no cell scale, its length is the F1
+    /// distance divided by the frequency.
+    pub fn get_nearest_offset<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM]
+    where
+        Self: WorleyFeatures<DIM>,
+    {
+        let mut offset = self.get_cell_point(point);
+        for (offset, value) in offset.iter_mut().zip(point.iter()) {
+            *offset -= value;
+        }
+
+        offset
+    }
+
+    /// Returns both the `ReturnType::Distance` and the `ReturnType::Value`
+    /// outputs for the input value, as `(distance, value)`, from a single
+    /// neighborhood search.
+    ///
+    /// This is equivalent to sampling two otherwise identical `Worley`
+    /// functions with different return types, at roughly half the cost, which
+    /// suits textures that combine cell shading with a per-cell color. If
+    /// smoothing is enabled, the distance is the smoothed distance, which
+    /// requires a separate search.
+    pub fn get_distance_and_value<const DIM: usize>(&self, point: [f64; DIM]) -> (f64, f64)
+    where
+        Self: WorleyFeatures<DIM>,
+    {
+        let nearest = self.nearest_feature(point);
+
+        let cell_hash = match self.tile_period {
+            Some(period) => PeriodicHasher::new(&self.perm_table, period).hash(&nearest.cell),
+            None => self.perm_table.hash(&nearest.cell),
+        };
+        let value = cell_hash as f64 / 255.0 * 2.0 - 1.0;
+
+        let distance = if self.smoothing > 0.0 {
+            self.smooth_distance(self.to_cell_space(point))
+        } else {
+            nearest.distance * 2.0 - 1.0
+        };
+
+        (distance, value)
+    }
+
+    /// Returns the number of cells per unit length along each axis, combining
+    /// the frequency and the cell scale.
+    fn cell_frequency(&self) -> [f64; 4] {
+        let mut frequency = [0.0; 4];
+        for (axis, frequency) in frequency.iter_mut().enumerate() {
+            *frequency = self.frequency / self.cell_scale[axis];
+        }
+
+        frequency
+    }
+
+    /// Maps an input value into cell space by applying the frequency and the
+    /// cell scale.
+    fn to_cell_space<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM] {
+        super::scale_axes(point, &self.cell_frequency())
+    }
+}
+
+impl Worley {
+    /// Default number of cells searched on either side of the input value's
+    /// cell by `get_k_nearest` and the smoothed distance.
+    pub const K_NEAREST_SEARCH_RADIUS: usize = 2;
+
+    /// Sets the number of cells searched on either side of the input value's
+    /// cell by `get_k_nearest` and the smoothed distance.
+    ///
+    /// The nearest feature point (F1) used by `get` is always found exactly,
+    /// since every feature point lies within half a cell of its cell's corner
+    /// and the standard search visits every cell that could hold it. Farther
+    /// feature points can lie outside the default neighborhood, so a larger
+    /// radius makes `get_k_nearest` exact for larger `K` and lets the smooth
+    /// minimum include more distant feature points, at the cost of visiting
+    /// `(2 * radius + 1)^DIM` cells per sample. The radius is at least one.
+    pub fn set_search_radius(self, search_radius: usize) -> Self {
+        Self {
+            search_radius: search_radius.max(1),
+            ..self
+        }
+    }
+
+    /// Returns the distances from the input value to the `K` nearest feature
+    /// points, in ascending order.
+    ///
+    /// The first entry is the distance used by `ReturnType::Distance` (often
+    /// called F1), the second is the distance to the second-nearest feature
+    /// point (F2), and so on, which allows combinations such as `F2 - F1` to be
+    /// built. Distances are measured in cell space, as returned by the distance
+    /// function, and are not remapped into `[-1, 1]`.
+    ///
+    /// The search visits every cell within the search radius of the input
+    /// value's cell, see `set_search_radius`. Entries beyond the number of
+    /// visited cells are set to infinity.
+    pub fn get_k_nearest<const DIM: usize, const K: usize>(&self, point: [f64; DIM]) -> [f64; K]
+    where
+        Self: WorleyFeatures<DIM>,
+    {
+        let scaled = self.to_cell_space(point);
+
+        let nearest: [Option<NearestFeature<DIM>>; K] = match self.tile_period {
+            Some(period) => k_nearest_features(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                scaled,
+                self.search_radius,
+            ),
+            None => k_nearest_features(
+                &self.perm_table,
+                &*self.distance_function,
+                scaled,
+                self.search_radius,
+            ),
+        };
+
+        let mut distances = [f64::INFINITY; K];
+        for (distance, feature) in distances.iter_mut().zip(nearest.iter()) {
+            if let Some(feature) = feature {
+                *distance = feature.distance;
+            }
+        }
+
+        distances
+    }
+
+    /// Returns the output for `ReturnType::Distance` with smoothing enabled,
+    /// for a point in cell space.
+    fn smooth_distance<const DIM: usize>(&self, point: [f64; DIM]) -> f64 {
+        let distance = match self.tile_period {
+            Some(period) => smooth_nearest_distance(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                point,
+                self.search_radius,
+                self.smoothing,
+            ),
+            None => smooth_nearest_distance(
+                &self.perm_table,
+                &*self.distance_function,
+                point,
+                self.search_radius,
+                self.smoothing,
+            ),
+        };
+
+        distance * 2.0 - 1.0
+    }
+
+    /// Returns the output with weighted cells, for a point in cell space.
+    fn weighted_output<const DIM: usize>(&self, point: [f64; DIM]) -> f64 {
+        match self.tile_period {
+            Some(period) => weighted_worley(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                self.return_type,
+                point,
+                self.search_radius,
+            ),
+            None => weighted_worley(
+                &self.perm_table,
+                &*self.distance_function,
+                self.return_type,
+                point,
+                self.search_radius,
+            ),
+        }
+    }
+
+    fn uses_smoothing(&self) -> bool {
+        matches!(self.return_type, ReturnType::Distance) && self.smoothing > 0.0
+    }
+}
+
+/// Access to the feature points found by the Worley neighborhood search.
+pub trait WorleyFeatures<const DIM: usize> {
+    /// Returns the feature point nearest to the input value.
+    ///
+    /// The returned feature is measured in cell space, that is, after the input
+    /// value has been scaled by the frequency and the cell scale.
+    fn nearest_feature(&self, point: [f64; DIM]) -> NearestFeature<DIM>;
+}
+
+macro_rules! impl_worley_features {
+    ($dim:expr, $vector:ident, $nearest:ident) => {
+        impl WorleyFeatures<$dim> for Worley {
+            fn nearest_feature(&self, point: [f64; $dim]) -> NearestFeature<$dim> {
+                if self.weighted {
+                    let point = self.to_cell_space(point);
+
+                    return match self.tile_period {
+                        Some(period) => weighted_nearest_feature(
+                            &PeriodicHasher::new(&self.perm_table, period),
+                            &*self.distance_function,
+                            point,
+                            self.search_radius,
+                        ),
+                        None => weighted_nearest_feature(
+                            &self.perm_table,
+                            &*self.distance_function,
+                            point,
+                            self.search_radius,
+                        ),
+                    };
+                }
+
+                let point = $vector::from(self.to_cell_space(point));
+
+                match self.tile_period {
+                    Some(period) => $nearest(
+                        &PeriodicHasher::new(&self.perm_table, period),
+                        &*self.distance_function,
+                        point,
+                    ),
+                    None => $nearest(&self.perm_table, &*self.distance_function, point),
+                }
+            }
+        }
+    };
 }
 
-fn worley_default_distance_fn() -> Rc<DistanceFunction> {
-    Rc::new(distance_functions::euclidean)
+impl_worley_features!(2, Vector2, nearest_feature_2d);
+impl_worley_features!(3, Vector3, nearest_feature_3d);
+impl_worley_features!(4, Vector4, nearest_feature_4d);
+
+fn worley_default_distance_fn() -> Arc<DistanceFunction> {
+    Arc::new(distance_functions::euclidean)
+}
+
+fn worley_default_search_radius() -> usize {
+    Worley::K_NEAREST_SEARCH_RADIUS
+}
+
+fn worley_default_cell_scale() -> [f64; 4] {
+    [1.0; 4]
 }
 
 impl Default for Worley {
@@ -112,34 +515,519 @@ impl Seedable for Worley {
 
 impl NoiseFn<f64, 2> for Worley {
     fn get(&self, point: [f64; 2]) -> f64 {
-        worley_2d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            Vector2::from(point) * self.frequency,
-        )
+        let point = Vector2::from(self.to_cell_space(point));
+
+        if self.uses_smoothing() {
+            return self.smooth_distance(point.into_array());
+        }
+
+        if self.weighted {
+            return self.weighted_output(point.into_array());
+        }
+
+        match self.tile_period {
+            Some(period) => worley_2d(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+            None => worley_2d(
+                &self.perm_table,
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+        }
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The most compressed axis has the most cells per unit.
+        Some(super::max_axis_frequency::<2>(&self.cell_frequency()))
+    }
+
+    fn describe(&self) -> String {
+        format!("Worley{{seed:{}, frequency:{}}}", self.seed, self.frequency)
     }
 }
 
 impl NoiseFn<f64, 3> for Worley {
     fn get(&self, point: [f64; 3]) -> f64 {
-        worley_3d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            Vector3::from(point) * self.frequency,
-        )
+        let point = Vector3::from(self.to_cell_space(point));
+
+        if self.uses_smoothing() {
+            return self.smooth_distance(point.into_array());
+        }
+
+        if self.weighted {
+            return self.weighted_output(point.into_array());
+        }
+
+        match self.tile_period {
+            Some(period) => worley_3d(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+            None => worley_3d(
+                &self.perm_table,
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+        }
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The most compressed axis has the most cells per unit.
+        Some(super::max_axis_frequency::<3>(&self.cell_frequency()))
+    }
+
+    fn describe(&self) -> String {
+        format!("Worley{{seed:{}, frequency:{}}}", self.seed, self.frequency)
     }
 }
 
 #[allow(clippy::cognitive_complexity)]
 impl NoiseFn<f64, 4> for Worley {
     fn get(&self, point: [f64; 4]) -> f64 {
-        worley_4d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            Vector4::from(point) * self.frequency,
-        )
+        let point = Vector4::from(self.to_cell_space(point));
+
+        if self.uses_smoothing() {
+            return self.smooth_distance(point.into_array());
+        }
+
+        if self.weighted {
+            return self.weighted_output(point.into_array());
+        }
+
+        match self.tile_period {
+            Some(period) => worley_4d(
+                &PeriodicHasher::new(&self.perm_table, period),
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+            None => worley_4d(
+                &self.perm_table,
+                &*self.distance_function,
+                self.return_type,
+                point,
+            ),
+        }
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The most compressed axis has the most cells per unit.
+        Some(super::max_axis_frequency::<4>(&self.cell_frequency()))
+    }
+
+    fn describe(&self) -> String {
+        format!("Worley{{seed:{}, frequency:{}}}", self.seed, self.frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Crackle;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn worley_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Worley>();
+        assert_send_sync::<Crackle>();
+    }
+
+    #[test]
+    fn cell_point_is_shared_within_cell() {
+        let worley = Worley::new(11).set_frequency(1.5);
+
+        for i in 0..40 {
+            let query = [i as f64 * 0.731 - 9.0, i as f64 * -0.417 + 4.0];
+            let cell_point = worley.get_cell_point(query);
+
+            // Voronoi cells are convex, so every point between the query and
+            // its feature point lies in the same cell.
+            for step in 0..=10 {
+                let t = step as f64 / 10.0;
+                let between = [
+                    query[0] + (cell_point[0] - query[0]) * t,
+                    query[1] + (cell_point[1] - query[1]) * t,
+                ];
+                assert_eq!(cell_point, worley.get_cell_point(between));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_has_requested_cell_count() {
+        let cells = 10;
+        let worley = Worley::new(5).for_grid(cells, Bounds::new(-3.0, 2.0).unwrap());
+
+        let mut owners = Vec::new();
+        let resolution = 400;
+        for y in 0..resolution {
+            for x in 0..resolution {
+                let point = [
+                    -3.0 + 5.0 * x as f64 / resolution as f64,
+                    -3.0 + 5.0 * y as f64 / resolution as f64,
+                ];
+                let cell = worley.nearest_feature(point).cell;
+                owners.push([
+                    cell[0].rem_euclid(cells as isize),
+                    cell[1].rem_euclid(cells as isize),
+                ]);
+            }
+        }
+        owners.sort_unstable();
+        owners.dedup();
+
+        assert_eq!(cells * cells, owners.len());
+    }
+
+    #[test]
+    fn k_nearest_distances_are_sorted() {
+        let worley = Worley::new(2)
+            .set_frequency(1.3)
+            .set_return_type(ReturnType::Distance);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.377 - 12.0, i as f64 * -0.291 + 3.0];
+            let distances: [f64; 4] = worley.get_k_nearest(point);
+
+            for pair in distances.windows(2) {
+                assert!(pair[0] <= pair[1]);
+            }
+
+            // F1 matches the distance output.
+            let f1 = (worley.get(point) + 1.0) / 2.0;
+            assert!(
+                (distances[0] - f1).abs() < 1e-12,
+                "{} != {}",
+                distances[0],
+                f1
+            );
+
+            // F2 matches an exhaustive search over a much larger neighborhood.
+            let scaled = [point[0] * 1.3, point[1] * 1.3];
+            let exhaustive: [Option<NearestFeature<2>>; 2] =
+                k_nearest_features(&worley.perm_table, distance_functions::euclidean, scaled, 6);
+            assert_eq!(exhaustive[1].unwrap().distance, distances[1]);
+        }
+    }
+
+    #[test]
+    fn squared_distance_is_square_of_euclidean() {
+        let euclidean = Worley::new(6).set_return_type(ReturnType::Distance);
+        let squared = euclidean
+            .clone()
+            .set_distance_function(distance_functions::euclidean_squared);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.173 - 7.0, i as f64 * 0.0937 + 2.0];
+            let distance = (euclidean.get(point) + 1.0) / 2.0;
+            let squared_distance = (squared.get(point) + 1.0) / 2.0;
+
+            assert!((distance * distance - squared_distance).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn tileable_matches_on_opposite_edges() {
+        for return_type in [ReturnType::Distance, ReturnType::Value] {
+            let worley = Worley::new(3)
+                .set_return_type(return_type)
+                .set_frequency(2.0)
+                .set_tileable(4);
+            // 4 cells at frequency 2.0 span 2.0 units of input space.
+            let size = 2.0;
+
+            for i in 0..64 {
+                let t = i as f64 / 64.0 * size;
+                assert!((worley.get([0.0, t]) - worley.get([size, t])).abs() < 1e-9);
+                assert!((worley.get([t, 0.0]) - worley.get([t, size])).abs() < 1e-9);
+                assert!((worley.get([t, 0.3, 0.0]) - worley.get([t, 0.3, size])).abs() < 1e-9);
+            }
+        }
+    }
+
+    /// Largest second difference of the output along a line, which is large
+    /// wherever the line crosses a crease.
+    fn max_curvature(worley: &Worley) -> f64 {
+        const STEP: f64 = 1e-3;
+
+        (1..5000)
+            .map(|i| {
+                let x = i as f64 * STEP;
+                let center = worley.get([x, 0.37]);
+                let second =
+                    worley.get([x - STEP, 0.37]) + worley.get([x + STEP, 0.37]) - 2.0 * center;
+                second.abs() / (STEP * STEP)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn smoothing_rounds_off_creases() {
+        let worley = Worley::new(5).set_return_type(ReturnType::Distance);
+
+        let hard = max_curvature(&worley);
+        let smooth = max_curvature(&worley.clone().set_smoothing(0.1));
+
+        assert!(smooth * 10.0 < hard, "hard: {}, smooth: {}", hard, smooth);
+    }
+
+    #[test]
+    fn vanishing_smoothing_matches_nearest_distance() {
+        let worley = Worley::new(5).set_return_type(ReturnType::Distance);
+        let smooth = worley.clone().set_smoothing(1e-6);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311];
+            assert!((worley.get(point) - smooth.get(point)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn cell_scale_stretches_cells() {
+        let worley = Worley::new(5).set_cell_scale([2.0, 1.0]);
+
+        // Count the cell borders crossed along rows and along columns of the
+        // same length.
+        let (mut along_x, mut along_y) = (0, 0);
+        for line in 0..40 {
+            let offset = line as f64 * 1.37;
+            for step in 0..400 {
+                let a = step as f64 * 0.05;
+                let b = a + 0.05;
+                if worley.get([a, offset]) != worley.get([b, offset]) {
+                    along_x += 1;
+                }
+                if worley.get([offset, a]) != worley.get([offset, b]) {
+                    along_y += 1;
+                }
+            }
+        }
+
+        let ratio = along_y as f64 / along_x as f64;
+        assert!(ratio > 1.6 && ratio < 2.5, "border ratio: {}", ratio);
+    }
+
+    #[test]
+    fn cell_scale_matches_scale_point() {
+        use crate::ScalePoint;
+
+        let worley = Worley::new(5).set_cell_scale([2.0, 4.0]);
+        let wrapped = ScalePoint::new(Worley::new(5))
+            .set_x_scale(0.5)
+            .set_y_scale(0.25);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311 + 2.0];
+            assert_eq!(wrapped.get(point), worley.get(point));
+
+            // The feature point is reported in input space, where it lies in
+            // the stretched cell of the input value.
+            let cell_point = worley.get_cell_point(point);
+            assert_eq!(cell_point, worley.get_cell_point(cell_point));
+        }
+    }
+
+    #[test]
+    fn distance_and_value_match_return_types() {
+        let value = Worley::new(21).set_frequency(1.7).set_tileable(6);
+        let distance = value.clone().set_return_type(ReturnType::Distance);
+
+        for i in 0..100 {
+            let point = [
+                i as f64 * 0.317 - 12.0,
+                i as f64 * -0.173 + 5.0,
+                i as f64 * 0.05,
+            ];
+            assert_eq!(
+                (distance.get(point), value.get(point)),
+                value.get_distance_and_value(point)
+            );
+        }
+    }
+
+    #[test]
+    fn chunk_boundaries_match() {
+        use crate::utils::{NoiseMapBuilder, PlaneMapBuilder};
+
+        const CHUNK_SIZE: usize = 64;
+
+        for seed in 0..4 {
+            for &spacing in &[0.031, 0.0625, 0.17] {
+                let value = Worley::new(seed).set_frequency(1.7);
+                let distance = value.clone().set_return_type(ReturnType::Distance);
+
+                for origin in [(-37, 11), (0, 0), (250, -96)] {
+                    // The last column of the first chunk is the first column
+                    // of its neighbour, which starts at a different origin.
+                    let neighbour = (origin.0 + CHUNK_SIZE as isize, origin.1);
+
+                    let chunk = |source: &Worley, origin| {
+                        PlaneMapBuilder::<_, 2>::new(source)
+                            .set_size(CHUNK_SIZE + 1, CHUNK_SIZE)
+                            .set_world_grid(origin, spacing)
+                            .build()
+                    };
+
+                    for source in [&value, &distance] {
+                        let (first, second) = (chunk(source, origin), chunk(source, neighbour));
+                        for y in 0..CHUNK_SIZE {
+                            assert_eq!(first[(CHUNK_SIZE, y)], second[(0, y)]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_feature_matches_exhaustive_search() {
+        let worley = Worley::new(17);
+
+        // The two closest corners along each axis do not always hold the
+        // nearest feature point. Here it belongs to a cell one further out.
+        let point = [-9.93301, 2.3940699999999997];
+        let exhaustive: [f64; 1] = worley.get_k_nearest(point);
+        assert!((exhaustive[0] - 0.6904844857055084).abs() < 1e-12);
+        assert_eq!(exhaustive[0], worley.nearest_feature(point).distance);
+
+        for i in 0..2000 {
+            let (x, y) = (i as f64 * 0.0731 - 60.0, i as f64 * -0.0417 + 20.0);
+
+            let point = [x, y];
+            let exhaustive: [f64; 1] = worley.get_k_nearest(point);
+            assert_eq!(exhaustive[0], worley.nearest_feature(point).distance);
+
+            let point = [x, y, x * 0.37];
+            let exhaustive: [f64; 1] = worley.get_k_nearest(point);
+            assert_eq!(exhaustive[0], worley.nearest_feature(point).distance);
+
+            let point = [x, y, x * 0.37, y * -0.61];
+            let exhaustive: [f64; 1] = worley.get_k_nearest(point);
+            assert_eq!(exhaustive[0], worley.nearest_feature(point).distance);
+        }
+    }
+
+    #[test]
+    fn search_radius_matches_brute_force() {
+        let worley = Worley::new(17)
+            .set_frequency(2.3)
+            .set_return_type(ReturnType::Distance);
+        let wide = worley.clone().set_search_radius(4);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.0731 - 6.0, i as f64 * -0.0417 + 2.0];
+            let scaled = [point[0] * 2.3, point[1] * 2.3];
+            let brute_force: [Option<NearestFeature<2>>; 8] =
+                k_nearest_features(&worley.perm_table, distance_functions::euclidean, scaled, 6);
+
+            // F1 is exact with the standard search.
+            let f1 = (worley.get(point) + 1.0) / 2.0;
+            assert!((brute_force[0].unwrap().distance - f1).abs() < 1e-12);
+
+            // Higher orders are exact once the search radius is wide enough.
+            let distances: [f64; 8] = wide.get_k_nearest(point);
+            for (distance, feature) in distances.iter().zip(brute_force.iter()) {
+                assert_eq!(feature.unwrap().distance, *distance);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_offset_points_at_feature_point() {
+        let worley = Worley::new(13)
+            .set_frequency(1.6)
+            .set_return_type(ReturnType::Distance);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 8.0, i as f64 * -0.311 + 3.0];
+            let offset = worley.get_nearest_offset(point);
+
+            let length = (offset[0] * offset[0] + offset[1] * offset[1]).sqrt();
+            let f1 = (worley.get(point) + 1.0) / 2.0;
+            assert!((length * 1.6 - f1).abs() < 1e-12);
+
+            let cell_point = worley.get_cell_point(point);
+            assert!((point[0] + offset[0] - cell_point[0]).abs() < 1e-12);
+            assert!((point[1] + offset[1] - cell_point[1]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn equal_axis_frequencies_match_scalar_frequency() {
+        let scalar = Worley::new(3).set_frequency(2.5);
+        let per_axis = Worley::new(3).set_frequency_per_axis([2.5, 2.5, 2.5]);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 4.0, i as f64 * -0.311, i as f64 * 0.05];
+            assert_eq!(scalar.get(point), per_axis.get(point));
+            assert_eq!(scalar.get_cell_point(point), per_axis.get_cell_point(point));
+        }
+
+        let stretched = Worley::new(3).set_frequency_per_axis([4.0, 1.0]);
+        assert_eq!(Some(4.0), NoiseFn::<f64, 2>::dominant_frequency(&stretched));
+    }
+
+    #[test]
+    fn weighting_moves_borders_between_cells() {
+        let unweighted = Worley::new(8).set_frequency(2.0);
+        let weighted = unweighted.clone().set_weighted(true);
+
+        let resolution = 120;
+        let mut changed = 0;
+        for y in 0..resolution {
+            for x in 0..resolution {
+                let point = [x as f64 * 0.05 - 3.0, y as f64 * 0.05 - 3.0];
+
+                let owner = unweighted.nearest_feature(point);
+                let weighted_owner = weighted.nearest_feature(point);
+                if owner.cell == weighted_owner.cell {
+                    continue;
+                }
+                changed += 1;
+
+                // With weights between 0.5 and 1.0, a cell can only be taken
+                // over by a neighbour at most twice as far away, so ownership
+                // only changes near the unweighted borders.
+                let [f1, f2] = unweighted.get_k_nearest::<2, 2>(point);
+                assert!(f2 < f1 * 2.0, "{} is far from a border", f2 / f1);
+
+                let value = weighted.clone().set_return_type(ReturnType::Value);
+                assert_eq!(value.get_distance_and_value(point).1, value.get(point));
+            }
+        }
+
+        let fraction = changed as f64 / (resolution * resolution) as f64;
+        assert!(
+            fraction > 0.05 && fraction < 0.5,
+            "{} of the samples changed owner",
+            fraction
+        );
+    }
+
+    #[test]
+    fn weighting_keeps_output_range() {
+        let unweighted = Worley::new(5)
+            .set_frequency(1.5)
+            .set_return_type(ReturnType::Distance);
+        let weighted = unweighted.clone().set_weighted(true);
+
+        for i in 0..20_000 {
+            let point = [i as f64 * 0.0173 - 150.0, (i % 257) as f64 * 0.039];
+
+            // The scaled weighted distance never exceeds the unweighted one,
+            // so the output stays within the unweighted range.
+            let (output, bound) = (weighted.get(point), unweighted.get(point));
+            assert!(output >= -1.0 && output <= bound, "{} > {}", output, bound);
+        }
     }
 }
@@ -0,0 +1,247 @@
+use crate::noise_fns::*;
+use alloc::boxed::Box;
+
+/// Serializable description of a graph of noise functions.
+///
+/// Trait objects such as `Box<dyn NoiseFn<f64, 2>>` carry no type information,
+/// so they cannot be serialized directly. A `NoiseGraph` describes the same
+/// graph as a tree of known node types instead. It serializes to a
+/// self-describing format with a `"type"` field naming each node, such as
+/// `{"type": "Add", "source1": {"type": "Perlin", ...}, "source2": ...}` in
+/// JSON, so graphs can be stored in data files. After loading, `build` turns
+/// the description into a boxed noise function of the required dimension.
+///
+/// Generators are stored as their own serialized form, while combinators,
+/// modifiers, selectors and transformers store their key parameters alongside
+/// their boxed sources. The fractals use `Perlin` octaves.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum NoiseGraph {
+    Checkerboard(Checkerboard),
+    Constant(Constant),
+    Cylinders(Cylinders),
+    OpenSimplex(OpenSimplex),
+    Perlin(Perlin),
+    PerlinSurflet(PerlinSurflet),
+    Simplex(Simplex),
+    Sine(Sine),
+    Value(Value),
+    Worley(Worley),
+
+    Fbm(Fbm<Perlin>),
+    Billow(Billow<Perlin>),
+    BasicMulti(BasicMulti<Perlin>),
+    HybridMulti(HybridMulti<Perlin>),
+    RidgedMulti(RidgedMulti<Perlin>),
+
+    Add {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+    },
+    Multiply {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+    },
+    Min {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+    },
+    Max {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+    },
+    Power {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+    },
+
+    Abs {
+        source: Box<NoiseGraph>,
+    },
+    Negate {
+        source: Box<NoiseGraph>,
+    },
+    Clamp {
+        source: Box<NoiseGraph>,
+        bounds: (f64, f64),
+    },
+    Exponent {
+        source: Box<NoiseGraph>,
+        exponent: f64,
+    },
+    ScaleBias {
+        source: Box<NoiseGraph>,
+        scale: f64,
+        bias: f64,
+    },
+
+    Blend {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+        control: Box<NoiseGraph>,
+    },
+    Select {
+        source1: Box<NoiseGraph>,
+        source2: Box<NoiseGraph>,
+        control: Box<NoiseGraph>,
+        bounds: (f64, f64),
+        falloff: f64,
+    },
+
+    ScalePoint {
+        source: Box<NoiseGraph>,
+        scale: [f64; 4],
+    },
+    TranslatePoint {
+        source: Box<NoiseGraph>,
+        translation: [f64; 4],
+    },
+}
+
+impl NoiseGraph {
+    /// Builds the noise function described by the graph.
+    pub fn build<const DIM: usize>(&self) -> Box<dyn NoiseFn<f64, DIM>>
+    where
+        Checkerboard: NoiseFn<f64, DIM>,
+        Cylinders: NoiseFn<f64, DIM>,
+        OpenSimplex: NoiseFn<f64, DIM>,
+        Perlin: NoiseFn<f64, DIM>,
+        PerlinSurflet: NoiseFn<f64, DIM>,
+        Simplex: NoiseFn<f64, DIM>,
+        Value: NoiseFn<f64, DIM>,
+        Worley: NoiseFn<f64, DIM>,
+        Fbm<Perlin>: NoiseFn<f64, DIM>,
+        Billow<Perlin>: NoiseFn<f64, DIM>,
+        BasicMulti<Perlin>: NoiseFn<f64, DIM>,
+        HybridMulti<Perlin>: NoiseFn<f64, DIM>,
+        RidgedMulti<Perlin>: NoiseFn<f64, DIM>,
+        ScalePoint<Box<dyn NoiseFn<f64, DIM>>>: NoiseFn<f64, DIM>,
+        TranslatePoint<Box<dyn NoiseFn<f64, DIM>>>: NoiseFn<f64, DIM>,
+    {
+        match self {
+            NoiseGraph::Checkerboard(node) => Box::new(*node),
+            NoiseGraph::Constant(node) => Box::new(*node),
+            NoiseGraph::Cylinders(node) => Box::new(*node),
+            NoiseGraph::OpenSimplex(node) => Box::new(*node),
+            NoiseGraph::Perlin(node) => Box::new(*node),
+            NoiseGraph::PerlinSurflet(node) => Box::new(*node),
+            NoiseGraph::Simplex(node) => Box::new(*node),
+            NoiseGraph::Sine(node) => Box::new(*node),
+            NoiseGraph::Value(node) => Box::new(*node),
+            NoiseGraph::Worley(node) => Box::new(node.clone()),
+
+            NoiseGraph::Fbm(node) => Box::new(node.clone()),
+            NoiseGraph::Billow(node) => Box::new(node.clone()),
+            NoiseGraph::BasicMulti(node) => Box::new(node.clone()),
+            NoiseGraph::HybridMulti(node) => Box::new(node.clone()),
+            NoiseGraph::RidgedMulti(node) => Box::new(node.clone()),
+
+            NoiseGraph::Add { source1, source2 } => {
+                Box::new(Add::new(source1.build(), source2.build()))
+            }
+            NoiseGraph::Multiply { source1, source2 } => {
+                Box::new(Multiply::new(source1.build(), source2.build()))
+            }
+            NoiseGraph::Min { source1, source2 } => {
+                Box::new(Min::new(source1.build(), source2.build()))
+            }
+            NoiseGraph::Max { source1, source2 } => {
+                Box::new(Max::new(source1.build(), source2.build()))
+            }
+            NoiseGraph::Power { source1, source2 } => {
+                Box::new(Power::new(source1.build(), source2.build()))
+            }
+
+            NoiseGraph::Abs { source } => Box::new(Abs::new(source.build())),
+            NoiseGraph::Negate { source } => Box::new(Negate::new(source.build())),
+            NoiseGraph::Clamp { source, bounds } => {
+                Box::new(Clamp::new(source.build()).set_bounds(bounds.0, bounds.1))
+            }
+            NoiseGraph::Exponent { source, exponent } => {
+                Box::new(Exponent::new(source.build()).set_exponent(*exponent))
+            }
+            NoiseGraph::ScaleBias {
+                source,
+                scale,
+                bias,
+            } => Box::new(
+                ScaleBias::new(source.build())
+                    .set_scale(*scale)
+                    .set_bias(*bias),
+            ),
+
+            NoiseGraph::Blend {
+                source1,
+                source2,
+                control,
+            } => Box::new(Blend::new(
+                source1.build(),
+                source2.build(),
+                control.build(),
+            )),
+            NoiseGraph::Select {
+                source1,
+                source2,
+                control,
+                bounds,
+                falloff,
+            } => Box::new(
+                Select::new(source1.build(), source2.build(), control.build())
+                    .set_bounds(bounds.0, bounds.1)
+                    .set_falloff(*falloff),
+            ),
+
+            NoiseGraph::ScalePoint { source, scale } => Box::new(
+                ScalePoint::new(source.build())
+                    .set_all_scales(scale[0], scale[1], scale[2], scale[3]),
+            ),
+            NoiseGraph::TranslatePoint {
+                source,
+                translation,
+            } => Box::new(TranslatePoint::new(source.build()).set_all_translations(
+                translation[0],
+                translation[1],
+                translation[2],
+                translation[3],
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn graph_round_trips_through_json() {
+        let graph = NoiseGraph::Add {
+            source1: Box::new(NoiseGraph::Perlin(Perlin::new(3))),
+            source2: Box::new(NoiseGraph::ScaleBias {
+                source: Box::new(NoiseGraph::Worley(
+                    Worley::new(5).set_frequency(2.0).set_tileable(8),
+                )),
+                scale: 0.5,
+                bias: -0.25,
+            }),
+        };
+
+        let json: String = serde_json::to_string(&graph).unwrap();
+        assert!(json.starts_with(r#"{"type":"Add","source1":{"type":"Perlin""#));
+
+        let reloaded: NoiseGraph = serde_json::from_str(&json).unwrap();
+        let expected = Add::new(
+            Perlin::new(3),
+            ScaleBias::new(Worley::new(5).set_frequency(2.0).set_tileable(8))
+                .set_scale(0.5)
+                .set_bias(-0.25),
+        );
+        let (original, reloaded) = (graph.build::<3>(), reloaded.build::<3>());
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311, i as f64 * 0.05];
+            assert_eq!(expected.get(point), original.get(point));
+            assert_eq!(original.get(point), reloaded.get(point));
+        }
+    }
+}
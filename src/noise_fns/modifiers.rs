@@ -1,9 +1,17 @@
-pub use self::{abs::*, clamp::*, curve::*, exponent::*, negate::*, scale_bias::*, terrace::*};
+pub use self::{
+    abs::*, clamp::*, curve::*, exponent::*, gamma::*, laplacian::*, lut::*, negate::*, plateau::*,
+    ridge::*, scale_bias::*, terrace::*,
+};
 
 mod abs;
 mod clamp;
 mod curve;
 mod exponent;
+mod gamma;
+mod laplacian;
+mod lut;
 mod negate;
+mod plateau;
+mod ridge;
 mod scale_bias;
 mod terrace;
@@ -1,4 +1,5 @@
 use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that outputs the absolute value of the output value from the
@@ -33,4 +34,32 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         (self.source.get(point)).abs()
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self.source.output_range()?;
+
+        if min >= 0.0 {
+            Some((min, max))
+        } else if max <= 0.0 {
+            Some((-max, -min))
+        } else {
+            Some((0.0, max.max(-min)))
+        }
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!("Abs({})", self.source.describe())
+    }
 }
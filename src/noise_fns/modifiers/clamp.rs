@@ -1,8 +1,16 @@
 use crate::noise_fns::NoiseFn;
-use core::marker::PhantomData;
+use alloc::{format, string::String};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 /// Noise function that clamps the output value from the source function to a
 /// range of values.
+///
+/// As a tuning aid, the function can optionally count how often the output
+/// value from the source function falls outside the clamping range. Counting
+/// is disabled by default, and is enabled with `enable_saturation_stats`.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Clamp<T, Source, const DIM: usize>
 where
@@ -14,9 +22,47 @@ where
     /// Bound of the clamping range. Default is -1.0 to 1.0.
     pub bounds: (f64, f64),
 
+    #[serde(skip)]
+    counters: Option<SaturationCounters>,
+
     phantom: PhantomData<T>,
 }
 
+/// How often a `Clamp` has clamped the output value from its source function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SaturationStats {
+    /// Number of output values recorded.
+    pub samples: u64,
+
+    /// Fraction of the recorded output values that were below the lower bound.
+    pub lower_fraction: f64,
+
+    /// Fraction of the recorded output values that were above the upper bound.
+    pub upper_fraction: f64,
+}
+
+/// Counters behind the saturation stats. They are atomic so that a `Clamp`
+/// stays `Sync` and can be sampled from several threads at once, and
+/// pointer-sized so that they exist on targets without 64-bit atomics.
+#[derive(Debug, Default)]
+struct SaturationCounters {
+    samples: AtomicUsize,
+    below: AtomicUsize,
+    above: AtomicUsize,
+}
+
+impl Clone for SaturationCounters {
+    fn clone(&self) -> Self {
+        let load = |counter: &AtomicUsize| AtomicUsize::new(counter.load(Ordering::Relaxed));
+
+        Self {
+            samples: load(&self.samples),
+            below: load(&self.below),
+            above: load(&self.above),
+        }
+    }
+}
+
 impl<T, Source, const DIM: usize> Clamp<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
@@ -25,6 +71,7 @@ where
         Self {
             source,
             bounds: (-1.0, 1.0),
+            counters: None,
             phantom: PhantomData,
         }
     }
@@ -49,6 +96,44 @@ where
             ..self
         }
     }
+
+    /// Starts counting how often the output value from the source function
+    /// falls outside the clamping range.
+    pub fn enable_saturation_stats(self) -> Self {
+        Self {
+            counters: Some(SaturationCounters::default()),
+            ..self
+        }
+    }
+
+    /// Returns how often the output value has been clamped since counting was
+    /// enabled or last reset, or `None` if counting is disabled.
+    pub fn saturation_stats(&self) -> Option<SaturationStats> {
+        let counters = self.counters.as_ref()?;
+        let samples = counters.samples.load(Ordering::Relaxed) as u64;
+        let fraction = |count: usize| {
+            if samples > 0 {
+                count as f64 / samples as f64
+            } else {
+                0.0
+            }
+        };
+
+        Some(SaturationStats {
+            samples,
+            lower_fraction: fraction(counters.below.load(Ordering::Relaxed)),
+            upper_fraction: fraction(counters.above.load(Ordering::Relaxed)),
+        })
+    }
+
+    /// Resets the saturation counters to zero.
+    pub fn reset_saturation_stats(&self) {
+        if let Some(counters) = &self.counters {
+            counters.samples.store(0, Ordering::Relaxed);
+            counters.below.store(0, Ordering::Relaxed);
+            counters.above.store(0, Ordering::Relaxed);
+        }
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Clamp<T, Source, DIM>
@@ -58,6 +143,100 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         let value = self.source.get(point);
 
+        if let Some(counters) = &self.counters {
+            counters.samples.fetch_add(1, Ordering::Relaxed);
+            if value < self.bounds.0 {
+                counters.below.fetch_add(1, Ordering::Relaxed);
+            } else if value > self.bounds.1 {
+                counters.above.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
         value.clamp(self.bounds.0, self.bounds.1)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some(self.bounds)
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Clamp{{bounds:({}, {})}}({})",
+            self.bounds.0,
+            self.bounds.1,
+            self.source.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Identity;
+
+    impl NoiseFn<f64, 1> for Identity {
+        fn get(&self, point: [f64; 1]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn saturation_stats_count_clamped_values() {
+        let clamp = Clamp::new(Identity).set_bounds(-1.0, 1.0);
+        clamp.get([5.0]);
+        assert_eq!(None, clamp.saturation_stats());
+
+        let clamp = clamp.enable_saturation_stats();
+        for &value in &[-2.0, -1.5, -1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 0.2, -0.2] {
+            clamp.get([value]);
+        }
+
+        let stats = clamp.saturation_stats().unwrap();
+        assert_eq!(10, stats.samples);
+        assert!((stats.lower_fraction - 0.2).abs() < 1e-12);
+        assert!((stats.upper_fraction - 0.2).abs() < 1e-12);
+
+        clamp.reset_saturation_stats();
+        assert_eq!(0, clamp.saturation_stats().unwrap().samples);
+    }
+
+    #[test]
+    fn saturation_stats_are_shared_between_threads() {
+        extern crate std;
+        use std::{sync::Arc, thread, vec::Vec};
+
+        let clamp = Arc::new(Clamp::new(Identity).enable_saturation_stats());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let clamp = Arc::clone(&clamp);
+                thread::spawn(move || {
+                    for i in 0..1024 {
+                        clamp.get([(i % 16) as f64 * 0.25 - 2.0]);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        // Each run of 16 values has four below -1.0 and three above 1.0.
+        let stats = clamp.saturation_stats().unwrap();
+        assert_eq!(4096, stats.samples);
+        assert_eq!(0.25, stats.lower_fraction);
+        assert_eq!(0.1875, stats.upper_fraction);
+    }
 }
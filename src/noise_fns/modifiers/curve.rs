@@ -1,6 +1,6 @@
 use crate::{math::interpolate, noise_fns::NoiseFn};
 use alloc::vec::Vec;
-use core::marker::PhantomData;
+use core::{convert::TryFrom, marker::PhantomData};
 
 /// Noise function that maps the output value from the source function onto an
 /// arbitrary function curve.
@@ -16,6 +16,13 @@ use core::marker::PhantomData;
 /// four control points to the curve. If there is less than four control
 /// points, the get() method panics. Each control point can have any input
 /// and output value, although no two control points can have the same input.
+///
+/// For large numbers of samples, the curve can be baked into a dense lookup
+/// table with the `bake` method. Each call to get() then costs a single table
+/// lookup and a linear interpolation, instead of a search through the control
+/// points and a cubic interpolation. The baked curve matches the exact curve to
+/// within the accuracy of the table's resolution. Values outside the range of
+/// the control points are clamped to the ends of the table.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Curve<T, Source, const DIM: usize>
 where
@@ -27,6 +34,10 @@ where
     /// Vec that stores the control points.
     control_points: Vec<ControlPoint<f64>>,
 
+    /// Dense samples of the curve, if it has been baked.
+    #[serde(default)]
+    lookup_table: Option<LookupTable>,
+
     phantom: PhantomData<T>,
 }
 
@@ -44,6 +55,7 @@ where
         Self {
             source,
             control_points: Vec::with_capacity(4),
+            lookup_table: None,
             phantom: PhantomData,
         }
     }
@@ -71,10 +83,109 @@ where
                     output: output_value,
                 },
             );
+
+            // keep a baked table in sync with the new control points.
+            if let Some(resolution) = self.lookup_table.as_ref().map(|t| t.values.len()) {
+                if self.control_points.len() >= 4 {
+                    self = self.bake(resolution);
+                }
+            }
         }
 
         self
     }
+
+    /// Bakes the curve into a lookup table with `resolution` evenly spaced
+    /// samples between the smallest and largest control point inputs.
+    ///
+    /// Control points added afterwards rebuild the table at the same
+    /// resolution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than four control points, or if `resolution`
+    /// is less than 2.
+    pub fn bake(self, resolution: usize) -> Self {
+        assert!(self.control_points.len() >= 4);
+        assert!(resolution >= 2);
+
+        let min = self.control_points[0].input;
+        let max = self.control_points[self.control_points.len() - 1].input;
+        let step = (max - min) / (resolution - 1) as f64;
+        let values = (0..resolution)
+            .map(|i| map_exact(&self.control_points, min + i as f64 * step))
+            .collect();
+
+        Self {
+            lookup_table: Some(LookupTable {
+                min,
+                scale: (resolution - 1) as f64 / (max - min),
+                values,
+            }),
+            ..self
+        }
+    }
+
+    /// Discards the baked lookup table, if any, returning to exact evaluation.
+    pub fn unbake(self) -> Self {
+        Self {
+            lookup_table: None,
+            ..self
+        }
+    }
+
+    /// Returns true if the curve has been baked into a lookup table.
+    pub fn is_baked(&self) -> bool {
+        self.lookup_table.is_some()
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "UncheckedLookupTable")]
+struct LookupTable {
+    min: f64,
+    /// Number of table entries per unit of input.
+    scale: f64,
+    values: Vec<f64>,
+}
+
+/// A deserialized `LookupTable` that has not yet been checked to hold the two
+/// entries every lookup interpolates between.
+#[derive(serde::Deserialize)]
+struct UncheckedLookupTable {
+    min: f64,
+    scale: f64,
+    values: Vec<f64>,
+}
+
+impl TryFrom<UncheckedLookupTable> for LookupTable {
+    type Error = &'static str;
+
+    fn try_from(table: UncheckedLookupTable) -> Result<Self, Self::Error> {
+        if table.values.len() < 2 {
+            return Err("a baked curve needs at least 2 table entries");
+        }
+
+        Ok(Self {
+            min: table.min,
+            scale: table.scale,
+            values: table.values,
+        })
+    }
+}
+
+impl LookupTable {
+    fn get(&self, value: f64) -> f64 {
+        let last = self.values.len() - 1;
+
+        // Values outside the range of the control points are clamped to the
+        // ends of the table.
+        let position = ((value - self.min) * self.scale).clamp(0.0, last as f64);
+        let index = (position as usize).min(last - 1);
+        let alpha = position - index as f64;
+
+        interpolate::linear(self.values[index], self.values[index + 1], alpha)
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Curve<T, Source, DIM>
@@ -88,51 +199,120 @@ where
         // get output value from the source function
         let source_value = self.source.get(point);
 
-        // Find the first element in the control point array that has a input
-        // value larger than the output value from the source function
-        let index_pos = self
-            .control_points
-            .iter()
-            .position(|x| x.input > source_value)
-            .unwrap_or(self.control_points.len());
-
-        // if index_pos < 2 {
-        //     println!(
-        //         "index_pos in curve was less than 2! source value was {}",
-        //         source_value
-        //     );
-        // }
-
-        // ensure that the index is at least 2 and less than control_points.len()
-        let index_pos = index_pos.clamp(2, self.control_points.len());
-
-        // Find the four nearest control points so that we can perform cubic
-        // interpolation.
-        let index0 = (index_pos - 2).clamp(0, self.control_points.len() - 1);
-        let index1 = (index_pos - 1).clamp(0, self.control_points.len() - 1);
-        let index2 = index_pos.clamp(0, self.control_points.len() - 1);
-        let index3 = (index_pos + 1).clamp(0, self.control_points.len() - 1);
-
-        // If some control points are missing (which occurs if the value from
-        // the source function is greater than the largest input value or less
-        // than the smallest input value of the control point array), get the
-        // corresponding output value of the nearest control point and exit.
-        if index1 == index2 {
-            return self.control_points[index1].output;
+        match &self.lookup_table {
+            Some(table) => table.get(source_value),
+            None => map_exact(&self.control_points, source_value),
         }
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+/// Maps `source_value` onto the cubic spline through `control_points`.
+fn map_exact(control_points: &[ControlPoint<f64>], source_value: f64) -> f64 {
+    // Find the first element in the control point array that has a input
+    // value larger than the output value from the source function
+    let index_pos = control_points
+        .iter()
+        .position(|x| x.input > source_value)
+        .unwrap_or(control_points.len());
+
+    // if index_pos < 2 {
+    //     println!(
+    //         "index_pos in curve was less than 2! source value was {}",
+    //         source_value
+    //     );
+    // }
+
+    // ensure that the index is at least 2 and less than control_points.len()
+    let index_pos = index_pos.clamp(2, control_points.len());
+
+    // Find the four nearest control points so that we can perform cubic
+    // interpolation.
+    let index0 = (index_pos - 2).clamp(0, control_points.len() - 1);
+    let index1 = (index_pos - 1).clamp(0, control_points.len() - 1);
+    let index2 = index_pos.clamp(0, control_points.len() - 1);
+    let index3 = (index_pos + 1).clamp(0, control_points.len() - 1);
+
+    // If some control points are missing (which occurs if the value from
+    // the source function is greater than the largest input value or less
+    // than the smallest input value of the control point array), get the
+    // corresponding output value of the nearest control point and exit.
+    if index1 == index2 {
+        return control_points[index1].output;
+    }
+
+    // Compute the alpha value used for cubic interpolation
+    let input0 = control_points[index1].input;
+    let input1 = control_points[index2].input;
+    let alpha = (source_value - input0) / (input1 - input0);
+
+    // Now perform the cubic interpolation and return.
+    interpolate::cubic(
+        control_points[index0].output,
+        control_points[index1].output,
+        control_points[index2].output,
+        control_points[index3].output,
+        alpha,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    fn curve() -> Curve<f64, Perlin, 2> {
+        Curve::new(Perlin::new(7))
+            .add_control_point(-1.0, -0.8)
+            .add_control_point(-0.3, 0.1)
+            .add_control_point(0.2, -0.2)
+            .add_control_point(0.6, 0.7)
+            .add_control_point(1.0, 1.0)
+    }
+
+    #[test]
+    fn baked_curve_matches_exact_curve() {
+        let exact = curve();
+        let baked = curve().bake(1024);
+        assert!(baked.is_baked());
+
+        for i in 0..500 {
+            let point = [i as f64 * 0.113, i as f64 * -0.071];
+            assert!((exact.get(point) - baked.get(point)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn adding_control_point_rebakes_table() {
+        let baked = curve().bake(64).add_control_point(0.0, 0.5);
+        let exact = curve().add_control_point(0.0, 0.5);
+
+        assert!(baked.is_baked());
+        let point = [0.0, 0.0];
+        assert!((exact.get(point) - baked.get(point)).abs() < 1e-2);
+        assert!(!baked.unbake().is_baked());
+    }
+
+    #[test]
+    fn short_lookup_tables_are_rejected() {
+        let baked = curve().bake(64);
+        let json = serde_json::to_string(&baked).unwrap();
+        let loaded: Curve<f64, Perlin, 2> = serde_json::from_str(&json).unwrap();
+        assert!(loaded.is_baked());
 
-        // Compute the alpha value used for cubic interpolation
-        let input0 = self.control_points[index1].input;
-        let input1 = self.control_points[index2].input;
-        let alpha = (source_value - input0) / (input1 - input0);
-
-        // Now perform the cubic interpolation and return.
-        interpolate::cubic(
-            self.control_points[index0].output,
-            self.control_points[index1].output,
-            self.control_points[index2].output,
-            self.control_points[index3].output,
-            alpha,
-        )
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["lookup_table"]["values"] = serde_json::json!([0.5]);
+        assert!(serde_json::from_value::<Curve<f64, Perlin, 2>>(value).is_err());
     }
 }
@@ -51,4 +51,16 @@ where
         value = value.powf(self.exponent);
         scale_shift(value, 2.0)
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
 }
@@ -0,0 +1,109 @@
+use crate::{math::signed_pow, noise_fns::NoiseFn};
+use core::marker::PhantomData;
+
+/// Noise function that applies gamma correction to the output value from the
+/// source function.
+///
+/// The output value is computed as `sign(x) * |x|^exponent`, which keeps the
+/// curve symmetric around zero. An exponent greater than 1.0 compresses values
+/// toward zero (producing more lowlands than peaks in a heightmap), while an
+/// exponent less than 1.0 pushes values outward toward -1.0 and 1.0.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Gamma<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Gamma exponent to apply to the output value from the source function.
+    /// Default is 1.0.
+    pub exponent: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Gamma<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            exponent: 1.0,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_exponent(self, exponent: f64) -> Self {
+        Self { exponent, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Gamma<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        signed_pow(self.source.get(point), self.exponent)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self.source.output_range()?;
+
+        // signed_pow is monotonic for positive exponents, so the bounds map
+        // directly onto the new bounds.
+        if self.exponent > 0.0 {
+            Some((
+                signed_pow(min, self.exponent),
+                signed_pow(max, self.exponent),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn gamma_of_one_is_identity() {
+        let perlin = Perlin::new(5);
+        let gamma = Gamma::new(perlin);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.13, i as f64 * -0.29];
+            assert_eq!(perlin.get(point), gamma.get(point));
+        }
+    }
+
+    #[test]
+    fn gamma_above_one_compresses_toward_zero() {
+        let perlin = Perlin::new(5);
+        let gamma = Gamma::new(perlin).set_exponent(2.2);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.13, i as f64 * -0.29];
+            let value = perlin.get(point);
+            let corrected = gamma.get(point);
+
+            assert!(corrected.abs() <= value.abs());
+            assert!(corrected * value >= 0.0);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the discrete Laplacian of the source function.
+///
+/// The Laplacian is estimated from the second difference of the source
+/// function at `±epsilon` along each axis. It is close to zero wherever the
+/// source function is flat or changes linearly, strongly negative on peaks and
+/// ridges, and strongly positive in pits and valleys, which makes it useful
+/// for extracting coastlines, ridge lines and contour-like features from a
+/// heightfield.
+///
+/// The output is normalized by the largest second difference the source
+/// function can produce, so it lies within `[-1, 1]`. The range reported by
+/// the source function is used for this when it is known, otherwise the source
+/// function is assumed to output values within `[-1, 1]`.
+///
+/// The `epsilon` parameter controls the scale of the features that are
+/// detected: small values respond to fine detail, while larger values respond
+/// to broader shapes. A step that suits one frequency gives poor results at
+/// another, so [`set_epsilon_for_frequency`](Self::set_epsilon_for_frequency)
+/// can be used to derive the step from the frequency of the source function,
+/// or [`set_epsilon_from_source`](Self::set_epsilon_from_source) to take that
+/// frequency from the source function itself.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Laplacian<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Distance from the input value at which the neighbouring samples are
+    /// taken. The default is set to 0.1.
+    pub epsilon: f64,
+}
+
+impl<Source> Laplacian<Source> {
+    pub const DEFAULT_EPSILON: f64 = 0.1;
+
+    /// Fraction of the source function's wavelength used as the step by
+    /// [`set_epsilon_for_frequency`](Self::set_epsilon_for_frequency).
+    pub const EPSILON_PER_WAVELENGTH: f64 = 0.1;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            epsilon: Self::DEFAULT_EPSILON,
+        }
+    }
+
+    pub fn set_epsilon(self, epsilon: f64) -> Self {
+        Self { epsilon, ..self }
+    }
+
+    /// Sets the step to a fixed fraction of the wavelength of a source function
+    /// with the given frequency, so that the same features are detected
+    /// regardless of how the source function is scaled.
+    pub fn set_epsilon_for_frequency(self, frequency: f64) -> Self {
+        self.set_epsilon(Self::EPSILON_PER_WAVELENGTH / frequency)
+    }
+
+    /// Sets the step from the [`dominant_frequency`](NoiseFn::dominant_frequency)
+    /// of the source function, as `set_epsilon_for_frequency` would. The step
+    /// is left unchanged if the frequency is unknown or not positive.
+    pub fn set_epsilon_from_source<const DIM: usize>(self) -> Self
+    where
+        Source: NoiseFn<f64, DIM>,
+    {
+        match self.source.dominant_frequency() {
+            Some(frequency) if frequency > 0.0 => self.set_epsilon_for_frequency(frequency),
+            _ => self,
+        }
+    }
+
+    /// Sums the second differences of the source function along each axis.
+    fn second_difference_sum<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        Source: NoiseFn<f64, DIM>,
+    {
+        let center = self.source.get(point);
+
+        let mut sum = 0.0;
+        for axis in 0..DIM {
+            let mut forward = point;
+            let mut backward = point;
+            forward[axis] += self.epsilon;
+            backward[axis] -= self.epsilon;
+
+            sum += self.source.get(forward) + self.source.get(backward) - 2.0 * center;
+        }
+
+        sum
+    }
+
+    /// Returns the unnormalized estimate of the Laplacian of the source
+    /// function at the input value.
+    ///
+    /// Unlike [`get`](NoiseFn::get), this is not rescaled into `[-1, 1]`, and
+    /// converges to the true Laplacian as `epsilon` shrinks.
+    pub fn laplacian<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        Source: NoiseFn<f64, DIM>,
+    {
+        self.second_difference_sum(point) / (self.epsilon * self.epsilon)
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Laplacian<Source>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let sum = self.second_difference_sum(point);
+
+        // Each second difference is bounded by twice the extent of the source
+        // range, in either direction.
+        let (min, max) = self.source.output_range().unwrap_or((-1.0, 1.0));
+        let bound = 2.0 * (max - min) * DIM as f64;
+
+        if bound > 0.0 {
+            (sum / bound).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        Some((-1.0, 1.0))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        // The center sample plus two samples along each axis.
+        self.source.sample_cost().saturating_mul(2 * DIM as u32 + 1)
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, MultiFractal, Perlin};
+    use alloc::vec::Vec;
+
+    struct Ramp;
+
+    impl NoiseFn<f64, 2> for Ramp {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            0.3 * point[0] - 0.2 * point[1]
+        }
+    }
+
+    struct Peak;
+
+    impl NoiseFn<f64, 2> for Peak {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            1.0 - 2.0 * (point[0] * point[0] + point[1] * point[1]).min(1.0)
+        }
+
+        fn output_range(&self) -> Option<(f64, f64)> {
+            Some((-1.0, 1.0))
+        }
+    }
+
+    struct Waves;
+
+    impl NoiseFn<f64, 2> for Waves {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            0.5 * (point[0].sin() + point[1].sin())
+        }
+    }
+
+    #[test]
+    fn estimate_converges_as_epsilon_shrinks() {
+        let point = [0.7_f64, -1.3];
+        let exact = -0.5 * (point[0].sin() + point[1].sin());
+
+        let errors: Vec<f64> = [0.4, 0.2, 0.1, 0.05]
+            .iter()
+            .map(|&epsilon| {
+                let laplacian = Laplacian::new(Waves).set_epsilon(epsilon);
+                (laplacian.laplacian(point) - exact).abs()
+            })
+            .collect();
+
+        for pair in errors.windows(2) {
+            assert!(pair[1] < pair[0]);
+        }
+        assert!(errors[3] < 1e-3);
+    }
+
+    #[test]
+    fn epsilon_follows_frequency() {
+        let laplacian = Laplacian::new(Waves).set_epsilon_for_frequency(4.0);
+
+        assert!((laplacian.epsilon - 0.025).abs() < 1e-12);
+
+        let laplacian =
+            Laplacian::new(Fbm::<Perlin>::new(0).set_frequency(4.0)).set_epsilon_from_source::<2>();
+        assert!((laplacian.epsilon - 0.025).abs() < 1e-12);
+
+        // Without a known frequency, the step is kept.
+        let laplacian = Laplacian::new(Waves).set_epsilon_from_source::<2>();
+        assert_eq!(Laplacian::<Waves>::DEFAULT_EPSILON, laplacian.epsilon);
+    }
+
+    #[test]
+    fn linear_ramp_has_no_curvature() {
+        let laplacian = Laplacian::new(Ramp);
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * -0.21 + 1.0];
+            assert!(laplacian.get(point).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn peak_gives_strong_negative_response() {
+        let laplacian = Laplacian::new(Peak).set_epsilon(0.5);
+
+        let at_peak = laplacian.get([0.0, 0.0]);
+        let away_from_peak = laplacian.get([3.0, 3.0]);
+
+        assert!(at_peak < -0.2, "response at peak: {}", at_peak);
+        assert_eq!(away_from_peak, 0.0);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{math::interpolate, noise_fns::NoiseFn};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Noise function that maps the output value from the source function through
+/// a lookup table.
+///
+/// The table entries are spread evenly over the range -1.0 to 1.0, so the
+/// first entry is the output for -1.0 and the last entry is the output for
+/// 1.0. Values between two entries are linearly interpolated, and values
+/// outside that range are clamped to the ends of the table.
+///
+/// Unlike [`Curve`](crate::Curve), which builds a spline from a few control
+/// points, this is intended for dense tables (256 or 1024 entries, for
+/// example) authored outside of the application.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Lut<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Entries of the table, from the output for -1.0 to the output for 1.0.
+    table: Vec<f64>,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Lut<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// # Panics
+    ///
+    /// Panics if `table` has less than two entries.
+    pub fn new(source: Source, table: Vec<f64>) -> Self {
+        Self {
+            source,
+            table: Vec::new(),
+            phantom: PhantomData,
+        }
+        .set_table(table)
+    }
+
+    /// Replaces the entries of the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` has less than two entries.
+    pub fn set_table(self, table: Vec<f64>) -> Self {
+        assert!(table.len() >= 2, "lookup table needs at least two entries");
+
+        Self { table, ..self }
+    }
+
+    pub fn table(&self) -> &[f64] {
+        &self.table
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Lut<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let last = self.table.len() - 1;
+
+        // Map the range -1.0 to 1.0 onto the indices of the table.
+        let position = ((self.source.get(point) + 1.0) * 0.5 * last as f64).clamp(0.0, last as f64);
+        let index = (position as usize).min(last - 1);
+        let alpha = position - index as f64;
+
+        interpolate::linear(self.table[index], self.table[index + 1], alpha)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        // The output is interpolated between entries, so it never leaves the
+        // range of the table.
+        let min = self.table.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.table.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some((min, max))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn linear_ramp_is_identity() {
+        let perlin = Perlin::new(9);
+
+        for &size in &[256, 1024] {
+            let ramp = (0..size)
+                .map(|i| i as f64 / (size - 1) as f64 * 2.0 - 1.0)
+                .collect();
+            let lut = Lut::new(perlin, ramp);
+
+            for i in 0..100 {
+                let point = [i as f64 * 0.137, i as f64 * -0.211];
+                assert!((perlin.get(point) - lut.get(point)).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that negates the output value from the source function.
@@ -32,4 +33,26 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         -self.source.get(point)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self.source.output_range()?;
+
+        Some((-max, -min))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!("Negate({})", self.source.describe())
+    }
 }
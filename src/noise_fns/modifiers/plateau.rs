@@ -0,0 +1,188 @@
+use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
+use core::marker::PhantomData;
+
+/// Noise function that flattens the output value from the source function
+/// below a floor and above a ceiling, such as to level off an ocean or a
+/// plateau in a heightmap.
+///
+/// Both levels are optional and disabled by default. Unlike `Clamp`, the
+/// levels can be joined to the rest of the output with a soft transition band,
+/// which replaces the crease at each level with a quadratic curve of the given
+/// width, centered on the level. Output values further than half the width
+/// beyond a level are flattened to the level exactly, and output values
+/// further than half the width inside are returned unchanged. The output and
+/// its slope are continuous across the band.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Plateau<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Level below which the output is flattened. Default is `None`.
+    pub floor: Option<f64>,
+
+    /// Level above which the output is flattened. Default is `None`.
+    pub ceiling: Option<f64>,
+
+    /// Width of the transition band around each level. Default is 0.0, which
+    /// flattens the output with a hard crease.
+    pub transition: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Plateau<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            floor: None,
+            ceiling: None,
+            transition: 0.0,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_floor(self, floor: f64) -> Self {
+        Self {
+            floor: Some(floor),
+            ..self
+        }
+    }
+
+    pub fn set_ceiling(self, ceiling: f64) -> Self {
+        Self {
+            ceiling: Some(ceiling),
+            ..self
+        }
+    }
+
+    pub fn set_transition(self, transition: f64) -> Self {
+        Self {
+            transition: transition.max(0.0),
+            ..self
+        }
+    }
+
+    /// Applies the floor and the ceiling to a value.
+    fn flatten(&self, mut value: f64) -> f64 {
+        let half = self.transition * 0.5;
+
+        if let Some(floor) = self.floor {
+            value = if value <= floor - half {
+                floor
+            } else if value >= floor + half {
+                value
+            } else {
+                let offset = value - floor + half;
+                floor + offset * offset / (2.0 * self.transition)
+            };
+        }
+
+        if let Some(ceiling) = self.ceiling {
+            value = if value >= ceiling + half {
+                ceiling
+            } else if value <= ceiling - half {
+                value
+            } else {
+                let offset = ceiling - value + half;
+                ceiling - offset * offset / (2.0 * self.transition)
+            };
+        }
+
+        value
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Plateau<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.flatten(self.source.get(point))
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        // Flattening never reverses the order of two values.
+        let (min, max) = self.source.output_range()?;
+        Some((self.flatten(min), self.flatten(max)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Plateau{{floor:{:?}, ceiling:{:?}, transition:{}}}({})",
+            self.floor,
+            self.ceiling,
+            self.transition,
+            self.source.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Identity;
+
+    impl NoiseFn<f64, 1> for Identity {
+        fn get(&self, point: [f64; 1]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn values_beyond_levels_are_flattened() {
+        let plateau = Plateau::new(Identity)
+            .set_floor(-0.2)
+            .set_ceiling(0.6)
+            .set_transition(0.1);
+
+        for &value in &[-1.0, -0.5, -0.26] {
+            assert_eq!(-0.2, plateau.get([value]));
+        }
+        for &value in &[0.66, 0.8, 1.0] {
+            assert_eq!(0.6, plateau.get([value]));
+        }
+        for &value in &[-0.1, 0.0, 0.3, 0.5] {
+            assert_eq!(value, plateau.get([value]));
+        }
+    }
+
+    #[test]
+    fn transition_band_is_continuous() {
+        let plateau = Plateau::new(Identity)
+            .set_floor(-0.2)
+            .set_ceiling(0.6)
+            .set_transition(0.2);
+
+        let step = 1e-4;
+        let mut previous = plateau.get([-1.0]);
+        for i in 1..=20000 {
+            let current = plateau.get([-1.0 + i as f64 * step]);
+
+            // The slope never exceeds that of the source, so the output can
+            // neither jump nor fall back.
+            assert!(current >= previous);
+            assert!(current - previous <= step * (1.0 + 1e-9));
+            previous = current;
+        }
+    }
+}
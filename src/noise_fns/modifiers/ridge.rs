@@ -0,0 +1,125 @@
+use crate::{math::signed_pow, noise_fns::NoiseFn};
+use core::marker::PhantomData;
+
+/// Noise function that folds the output value from the source function into
+/// ridges.
+///
+/// The output value is computed as `(1 - |x|)^sharpness`, which is the
+/// transform `RidgedMulti` applies to each of its octaves, so sharp ridges form
+/// wherever the source function crosses zero. Unlike `RidgedMulti`, this can
+/// be applied to any source function, such as `Worley` or `Value`.
+///
+/// For source values within `[-1, 1]` the output lies within `[0, 1]`. Source
+/// values outside that range produce negative output values.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Ridge<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Exponent applied to the ridges. Higher values produce sharper, thinner
+    /// ridges. Default is 2.0, which matches `RidgedMulti`.
+    pub sharpness: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Ridge<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_SHARPNESS: f64 = 2.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            sharpness: Self::DEFAULT_SHARPNESS,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_sharpness(self, sharpness: f64) -> Self {
+        Self { sharpness, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Ridge<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        signed_pow(1.0 - self.source.get(point).abs(), self.sharpness)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self.source.output_range()?;
+
+        let (abs_min, abs_max) = if min >= 0.0 {
+            (min, max)
+        } else if max <= 0.0 {
+            (-max, -min)
+        } else {
+            (0.0, max.max(-min))
+        };
+
+        // signed_pow is monotonic for positive exponents, so the bounds map
+        // directly onto the new bounds.
+        if self.sharpness > 0.0 {
+            Some((
+                signed_pow(1.0 - abs_max, self.sharpness),
+                signed_pow(1.0 - abs_min, self.sharpness),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MultiFractal, Perlin, RidgedMulti};
+
+    #[test]
+    fn matches_single_octave_ridged_multi() {
+        let ridged = RidgedMulti::<Perlin>::new(0).set_octaves(1);
+        let ridge = Ridge::new(Perlin::new(0));
+
+        // A single octave of RidgedMulti is the ridge profile rescaled by the
+        // fractal's scale factor and shifted down by one.
+        let reference = [0.3, 0.4];
+        let scale = (ridged.get(reference) + 1.0) / ridge.get(reference);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.213, i as f64 * -0.117];
+            let expected = ridge.get(point) * scale - 1.0;
+            assert!((ridged.get(point) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn linear_ridge_twice_is_abs() {
+        let perlin = Perlin::new(2);
+        let twice = Ridge::new(Ridge::new(perlin).set_sharpness(1.0)).set_sharpness(1.0);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.213, i as f64 * -0.117];
+            assert!((twice.get(point) - perlin.get(point).abs()).abs() < 1e-12);
+        }
+        assert_eq!(Some((0.0, 1.0)), NoiseFn::<f64, 2>::output_range(&twice));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that applies a scaling factor and a bias to the output value
@@ -57,4 +58,34 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         (self.source.get(point) * self.scale) + self.bias
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self.source.output_range()?;
+
+        let a = min * self.scale + self.bias;
+        let b = max * self.scale + self.bias;
+
+        Some((a.min(b), a.max(b)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "ScaleBias{{scale:{}, bias:{}}}({})",
+            self.scale,
+            self.bias,
+            self.source.describe()
+        )
+    }
 }
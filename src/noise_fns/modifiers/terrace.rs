@@ -144,6 +144,18 @@ where
         // Now perform the cubic interpolation and return.
         interpolate::linear(input0, input1, alpha)
     }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
 }
 
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
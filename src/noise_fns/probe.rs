@@ -0,0 +1,214 @@
+use crate::noise_fns::NoiseFn;
+use alloc::{format, string::String};
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Noise function that records the smallest and largest output values
+/// generated by the source function.
+///
+/// Every call to `Probe::get` forwards to `Source::get` and updates the running
+/// minimum and maximum. This is useful as a calibration pass: sample a
+/// representative region of a noise function graph, read the range it actually
+/// produces with [`observed_range`](Self::observed_range), and use that range
+/// to configure a `ScaleBias` or `Clamp` further down the graph.
+///
+/// The recorded range is shared between threads sampling the same probe, and
+/// is not serialized. The probe is only available on targets with 64-bit
+/// atomics.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Probe<Source> {
+    /// Outputs the value to be recorded.
+    pub source: Source,
+
+    #[serde(skip, default = "AtomicF64::infinity")]
+    min: AtomicF64,
+
+    #[serde(skip, default = "AtomicF64::neg_infinity")]
+    max: AtomicF64,
+}
+
+/// An `f64` stored as its bit pattern in an `AtomicU64`, so that a `Probe`
+/// stays `Sync` and can be sampled from several threads at once.
+struct AtomicF64(AtomicU64);
+
+impl AtomicF64 {
+    fn new(value: f64) -> Self {
+        Self(AtomicU64::new(value.to_bits()))
+    }
+
+    fn infinity() -> Self {
+        Self::new(f64::INFINITY)
+    }
+
+    fn neg_infinity() -> Self {
+        Self::new(f64::NEG_INFINITY)
+    }
+
+    fn load(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn store(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Replaces the stored value with `value` for as long as `replaces`
+    /// returns true for the two.
+    fn update(&self, value: f64, replaces: impl Fn(f64, f64) -> bool) {
+        let mut current = self.0.load(Ordering::Relaxed);
+        while replaces(value, f64::from_bits(current)) {
+            match self.0.compare_exchange_weak(
+                current,
+                value.to_bits(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl Clone for AtomicF64 {
+    fn clone(&self) -> Self {
+        Self::new(self.load())
+    }
+}
+
+impl fmt::Debug for AtomicF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.load().fmt(f)
+    }
+}
+
+impl<Source> Probe<Source> {
+    pub fn new(source: Source) -> Self {
+        Probe {
+            source,
+            min: AtomicF64::infinity(),
+            max: AtomicF64::neg_infinity(),
+        }
+    }
+
+    /// Returns the `(min, max)` range of the output values seen so far, or
+    /// `None` if the probe has not been sampled since it was created or reset.
+    pub fn observed_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = (self.min.load(), self.max.load());
+
+        if min <= max {
+            Some((min, max))
+        } else {
+            None
+        }
+    }
+
+    /// Forgets the output values seen so far.
+    pub fn reset(&self) {
+        self.min.store(f64::INFINITY);
+        self.max.store(f64::NEG_INFINITY);
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Probe<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let value = self.source.get(point);
+
+        // NaN compares false with everything, so it never widens the range.
+        self.min.update(value, |value, min| value < min);
+        self.max.update(value, |value, max| value > max);
+
+        value
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+
+    fn describe(&self) -> String {
+        format!("Probe({})", self.source.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    struct Sine;
+
+    impl NoiseFn<f64, 2> for Sine {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            0.8 * point[0].sin() + 0.1
+        }
+    }
+
+    #[test]
+    fn records_extremes_of_sampled_values() {
+        let probe = Probe::new(Sine);
+        assert_eq!(probe.observed_range(), None);
+
+        for i in 0..1000 {
+            probe.get([i as f64 * 0.01, 0.0]);
+        }
+
+        let (min, max) = probe.observed_range().unwrap();
+        assert!((min + 0.7).abs() < 1e-3, "min: {}", min);
+        assert!((max - 0.9).abs() < 1e-3, "max: {}", max);
+
+        probe.reset();
+        assert_eq!(probe.observed_range(), None);
+    }
+
+    #[test]
+    fn serialized_probe_starts_empty() {
+        let probe = Probe::new(Perlin::new(1));
+        probe.get([0.3, 0.7]);
+
+        let json = serde_json::to_string(&probe).unwrap();
+        let probe: Probe<Perlin> = serde_json::from_str(&json).unwrap();
+        assert_eq!(probe.observed_range(), None);
+    }
+
+    #[test]
+    fn records_extremes_across_threads() {
+        extern crate std;
+        use std::{sync::Arc, thread, vec::Vec};
+
+        let probe = Arc::new(Probe::new(Sine));
+        let threads: Vec<_> = (0..4)
+            .map(|offset| {
+                let probe = Arc::clone(&probe);
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        probe.get([(i * 4 + offset) as f64 * 0.0025, 0.0]);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let (min, max) = probe.observed_range().unwrap();
+        assert!((min + 0.7).abs() < 1e-3, "min: {}", min);
+        assert!((max - 0.9).abs() < 1e-3, "max: {}", max);
+    }
+}
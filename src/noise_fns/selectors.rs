@@ -1,4 +1,6 @@
-pub use self::{blend::*, select::*};
+pub use self::{blend::*, blend_seed::*, lerp::*, select::*};
 
 mod blend;
+mod blend_seed;
+mod lerp;
 mod select;
@@ -1,11 +1,17 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+use crate::{
+    math::interpolate,
+    noise_fns::{guard_non_finite, max_frequency, NoiseFn},
+};
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that outputs a weighted blend of the output values from two
 /// source functions given the output value supplied by a control function.
 ///
 /// This noise function uses linear interpolation to perform the blending
-/// operation.
+/// operation. Where the control value is exactly 0.0 or 1.0, such as in the
+/// flat regions of a clamped control function, only the source function whose
+/// output is returned is evaluated.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Blend<T, Source1, Source2, Control, const DIM: usize>
 where
@@ -24,6 +30,10 @@ where
     /// values weight the blend towards the output value from the `source2`
     /// function.
     pub control: Control,
+    /// Value that replaces NaN or infinite outputs from the source and control
+    /// functions, or `None` to pass them through unchanged. Default is `None`.
+    #[serde(default)]
+    pub guard_non_finite: Option<f64>,
 
     phantom: PhantomData<T>,
 }
@@ -39,9 +49,20 @@ where
             source1,
             source2,
             control,
+            guard_non_finite: None,
             phantom: PhantomData,
         }
     }
+
+    /// Replaces NaN or infinite outputs from the source and control functions
+    /// with the given value before they are used, so that a misbehaving source
+    /// does not poison the rest of the graph. `None` disables the guard.
+    pub fn set_guard_non_finite(self, replacement: Option<f64>) -> Self {
+        Blend {
+            guard_non_finite: replacement,
+            ..self
+        }
+    }
 }
 
 impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
@@ -53,10 +74,104 @@ where
     Control: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        let lower = self.source1.get(point);
-        let upper = self.source2.get(point);
-        let control = self.control.get(point);
+        let control = guard_non_finite(self.control.get(point), self.guard_non_finite);
+        let source1 = || guard_non_finite(self.source1.get(point), self.guard_non_finite);
+        let source2 = || guard_non_finite(self.source2.get(point), self.guard_non_finite);
+
+        // The interpolation returns one of the sources unchanged at either end,
+        // so the other source does not need to be evaluated.
+        if control == 0.0 {
+            return source1();
+        }
+        if control == 1.0 {
+            return source2();
+        }
+
+        let lower = source1();
+        let upper = source2();
 
         interpolate::linear(lower, upper, control)
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+        let (control_min, control_max) = self.control.output_range()?;
+
+        // The blend is linear in each of its inputs, so its extremes lie at the
+        // corners of the input ranges.
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for &control in &[control_min, control_max] {
+            for &lower in &[min1, max1] {
+                for &upper in &[min2, max2] {
+                    let value = interpolate::linear(lower, upper, control);
+                    min = min.min(value);
+                    max = max.max(value);
+                }
+            }
+        }
+
+        Some((min, max))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+            .saturating_add(self.control.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+            self.control.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+            self.control.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Blend({}, {}, {})",
+            self.source1.describe(),
+            self.source2.describe(),
+            self.control.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn endpoints_match_full_evaluation() {
+        let source1 = Perlin::new(1);
+        let source2 = Perlin::new(2);
+
+        for &control in &[0.0, 1.0] {
+            let blend = Blend::new(source1, source2, Constant::new(control));
+
+            for i in 0..50 {
+                let point = [i as f64 * 0.173, i as f64 * -0.311];
+                let expected = interpolate::linear(source1.get(point), source2.get(point), control);
+                assert_eq!(expected, blend.get(point));
+            }
+        }
+    }
 }
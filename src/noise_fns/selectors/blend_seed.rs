@@ -0,0 +1,182 @@
+use crate::noise_fns::{max_frequency, NoiseFn, Seedable};
+use alloc::{format, string::String};
+
+/// Noise function that crossfades between two copies of the same generator
+/// seeded differently.
+///
+/// This is intended for transitions between regions keyed by different seeds,
+/// such as biome boundaries, where switching the seed directly would cause a
+/// visible pop. The output is `(1 - weight) * a + weight * b`, which returns the
+/// first field exactly at a weight of 0.0 and the second field exactly at a
+/// weight of 1.0. The weight is clamped to `[0, 1]`.
+///
+/// Because both fields are centered on zero, the blend stays centered on zero
+/// for every weight. Two unrelated fields partially cancel each other out, so
+/// the contrast of the output dips around a weight of 0.5. Enable
+/// `preserve_contrast` to rescale the blend by `1 / sqrt((1 - w)^2 + w^2)`,
+/// which keeps the spread of the output constant across the fade at the cost of
+/// occasionally exceeding the range of the source generator.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BlendSeed<Source> {
+    /// Field returned at a weight of 0.0.
+    pub source1: Source,
+
+    /// Field returned at a weight of 1.0.
+    pub source2: Source,
+
+    /// Position of the crossfade between `source1` and `source2`. Default is
+    /// 0.0.
+    pub weight: f64,
+
+    /// Whether the blend is rescaled to keep its contrast constant. Default
+    /// is false.
+    pub preserve_contrast: bool,
+}
+
+impl<Source> BlendSeed<Source>
+where
+    Source: Default + Seedable,
+{
+    /// Creates a crossfade between the default generator seeded with `seed1`
+    /// and the default generator seeded with `seed2`.
+    pub fn new(seed1: u32, seed2: u32) -> Self {
+        Self::from_sources(
+            Source::default().set_seed(seed1),
+            Source::default().set_seed(seed2),
+        )
+    }
+}
+
+impl<Source> BlendSeed<Source> {
+    /// Creates a crossfade between two already configured generators.
+    pub fn from_sources(source1: Source, source2: Source) -> Self {
+        Self {
+            source1,
+            source2,
+            weight: 0.0,
+            preserve_contrast: false,
+        }
+    }
+
+    pub fn set_weight(self, weight: f64) -> Self {
+        Self {
+            weight: weight.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn set_preserve_contrast(self, preserve_contrast: bool) -> Self {
+        Self {
+            preserve_contrast,
+            ..self
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for BlendSeed<Source>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let weight = self.weight;
+
+        // Skip the unused field at the endpoints, which also guarantees that
+        // they are reproduced exactly.
+        if weight <= 0.0 {
+            return self.source1.get(point);
+        }
+        if weight >= 1.0 {
+            return self.source2.get(point);
+        }
+
+        let value = (1.0 - weight) * self.source1.get(point) + weight * self.source2.get(point);
+
+        if self.preserve_contrast {
+            value / ((1.0 - weight) * (1.0 - weight) + weight * weight).sqrt()
+        } else {
+            value
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+        let weight = self.weight;
+
+        let (min, max) = (
+            (1.0 - weight) * min1 + weight * min2,
+            (1.0 - weight) * max1 + weight * max2,
+        );
+
+        if self.preserve_contrast && weight > 0.0 && weight < 1.0 {
+            let scale = ((1.0 - weight) * (1.0 - weight) + weight * weight).sqrt();
+            Some((min / scale, max / scale))
+        } else {
+            Some((min, max))
+        }
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "BlendSeed{{weight:{}}}({}, {})",
+            self.weight,
+            self.source1.describe(),
+            self.source2.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn endpoints_match_source_fields() {
+        let first = Perlin::new(3);
+        let second = Perlin::new(97);
+        let blend = BlendSeed::<Perlin>::new(3, 97);
+        let start = blend.clone().set_weight(0.0);
+        let end = blend.set_weight(1.0);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311];
+            assert_eq!(start.get(point), first.get(point));
+            assert_eq!(end.get(point), second.get(point));
+        }
+    }
+
+    #[test]
+    fn midpoint_is_average_of_fields() {
+        let first = Perlin::new(3);
+        let second = Perlin::new(97);
+        let blend = BlendSeed::from_sources(first, second).set_weight(0.5);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311, 0.5];
+            let expected = 0.5 * first.get(point) + 0.5 * second.get(point);
+            assert!((blend.get(point) - expected).abs() < 1e-12);
+        }
+    }
+}
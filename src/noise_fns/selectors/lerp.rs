@@ -0,0 +1,158 @@
+use crate::noise_fns::{max_frequency, NoiseFn};
+use alloc::{format, string::String};
+use core::marker::PhantomData;
+
+/// Noise function that linearly interpolates between the output values from two
+/// source functions, using the output value of a weight function as the
+/// interpolation factor.
+///
+/// The output is `a + (b - a) * weight`, where the weight is clamped to
+/// `[0, 1]` and is otherwise used as is. This makes `Lerp` predictable when the
+/// weight function is purpose-built to output values within `[0, 1]`, such as
+/// a mask, whereas `Blend` treats its control value as a signed weight. Where
+/// the clamped weight is exactly 0.0 or 1.0, only the source function whose
+/// output is returned is evaluated.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Lerp<T, A, B, Weight, const DIM: usize>
+where
+    A: NoiseFn<T, DIM>,
+    B: NoiseFn<T, DIM>,
+    Weight: NoiseFn<T, DIM>,
+{
+    /// Outputs the value returned at a weight of 0.0.
+    pub a: A,
+
+    /// Outputs the value returned at a weight of 1.0.
+    pub b: B,
+
+    /// Outputs the interpolation factor between `a` and `b`.
+    pub weight: Weight,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, A, B, Weight, const DIM: usize> Lerp<T, A, B, Weight, DIM>
+where
+    A: NoiseFn<T, DIM>,
+    B: NoiseFn<T, DIM>,
+    Weight: NoiseFn<T, DIM>,
+{
+    pub fn new(a: A, b: B, weight: Weight) -> Self {
+        Lerp {
+            a,
+            b,
+            weight,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, A, B, Weight, const DIM: usize> NoiseFn<T, DIM> for Lerp<T, A, B, Weight, DIM>
+where
+    T: Copy,
+    A: NoiseFn<T, DIM>,
+    B: NoiseFn<T, DIM>,
+    Weight: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let weight = self.weight.get(point).clamp(0.0, 1.0);
+
+        if weight == 0.0 {
+            return self.a.get(point);
+        }
+        if weight == 1.0 {
+            return self.b.get(point);
+        }
+
+        let a = self.a.get(point);
+        let b = self.b.get(point);
+
+        a + (b - a) * weight
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min_a, max_a) = self.a.output_range()?;
+        let (min_b, max_b) = self.b.output_range()?;
+
+        // Any weight within [0, 1] gives a value between the two sources.
+        Some((min_a.min(min_b), max_a.max(max_b)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.a
+            .sample_cost()
+            .saturating_add(self.b.sample_cost())
+            .saturating_add(self.weight.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.a.dominant_frequency(),
+            self.b.dominant_frequency(),
+            self.weight.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.a.highest_frequency(),
+            self.b.highest_frequency(),
+            self.weight.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Lerp({}, {}, {})",
+            self.a.describe(),
+            self.b.describe(),
+            self.weight.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn constant_weight_interpolates_linearly() {
+        let a = Perlin::new(1);
+        let b = Perlin::new(2);
+
+        for &weight in &[0.25, 0.5, 0.8] {
+            let lerp = Lerp::new(a, b, Constant::new(weight));
+
+            for i in 0..50 {
+                let point = [i as f64 * 0.173, i as f64 * -0.311];
+                let expected = a.get(point) + (b.get(point) - a.get(point)) * weight;
+                assert_eq!(expected, lerp.get(point));
+            }
+        }
+
+        // The endpoints reproduce the sources exactly.
+        let start = Lerp::new(a, b, Constant::new(0.0));
+        let end = Lerp::new(a, b, Constant::new(1.0));
+        for i in 0..50 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311];
+            assert_eq!(a.get(point), start.get(point));
+            assert_eq!(b.get(point), end.get(point));
+        }
+    }
+
+    #[test]
+    fn weight_is_clamped() {
+        let below = Lerp::new(Constant::new(-0.5), Constant::new(0.5), Constant::new(-2.0));
+        let above = Lerp::new(Constant::new(-0.5), Constant::new(0.5), Constant::new(3.0));
+
+        assert_eq!(-0.5, NoiseFn::<f64, 2>::get(&below, [0.3, 0.7]));
+        assert_eq!(0.5, NoiseFn::<f64, 2>::get(&above, [0.3, 0.7]));
+    }
+}
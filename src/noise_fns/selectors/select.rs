@@ -1,11 +1,16 @@
 use crate::{
     math::{interpolate, s_curve::cubic::Cubic},
-    noise_fns::NoiseFn,
+    noise_fns::{guard_non_finite, max_frequency, NoiseFn},
 };
+use alloc::{format, string::String};
 use core::marker::PhantomData;
 
 /// Noise function that outputs the value selected from one of two source
 /// functions chosen by the output value from a control function.
+///
+/// Only the source function whose output is used is evaluated. Both source
+/// functions are only evaluated where the control value lies within the
+/// falloff band around either bound, where their outputs are blended.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Select<T, Source1, Source2, Control, const DIM: usize>
 where
@@ -30,6 +35,10 @@ where
 
     /// Edge falloff value. Default is 0.0.
     pub falloff: f64,
+    /// Value that replaces NaN or infinite outputs from the source and control
+    /// functions, or `None` to pass them through unchanged. Default is `None`.
+    #[serde(default)]
+    pub guard_non_finite: Option<f64>,
 
     phantom: PhantomData<T>,
 }
@@ -47,6 +56,7 @@ where
             control,
             bounds: (0.0, 1.0),
             falloff: 0.0,
+            guard_non_finite: None,
             phantom: PhantomData,
         }
     }
@@ -61,6 +71,16 @@ where
     pub fn set_falloff(self, falloff: f64) -> Self {
         Select { falloff, ..self }
     }
+
+    /// Replaces NaN or infinite outputs from the source and control functions
+    /// with the given value before they are used, so that a misbehaving source
+    /// does not poison the rest of the graph. `None` disables the guard.
+    pub fn set_guard_non_finite(self, replacement: Option<f64>) -> Self {
+        Select {
+            guard_non_finite: replacement,
+            ..self
+        }
+    }
 }
 
 impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
@@ -72,35 +92,139 @@ where
     Control: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        let control_value = self.control.get(point);
+        let control_value = guard_non_finite(self.control.get(point), self.guard_non_finite);
+        let source1 = || guard_non_finite(self.source1.get(point), self.guard_non_finite);
+        let source2 = || guard_non_finite(self.source2.get(point), self.guard_non_finite);
         let (lower, upper) = self.bounds;
 
         if self.falloff > 0.0 {
             match () {
-                _ if control_value < (lower - self.falloff) => self.source1.get(point),
+                _ if control_value < (lower - self.falloff) => source1(),
                 _ if control_value < (lower + self.falloff) => {
                     let lower_curve = lower - self.falloff;
                     let upper_curve = lower + self.falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
-                    interpolate::linear(self.source1.get(point), self.source2.get(point), alpha)
+                    interpolate::linear(source1(), source2(), alpha)
                 }
-                _ if control_value < (upper - self.falloff) => self.source2.get(point),
+                _ if control_value < (upper - self.falloff) => source2(),
                 _ if control_value < (upper + self.falloff) => {
                     let lower_curve = upper - self.falloff;
                     let upper_curve = upper + self.falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
-                    interpolate::linear(self.source2.get(point), self.source1.get(point), alpha)
+                    interpolate::linear(source2(), source1(), alpha)
                 }
-                _ => self.source1.get(point),
+                _ => source1(),
             }
         } else if control_value < lower || control_value > upper {
-            self.source1.get(point)
+            source1()
         } else {
-            self.source2.get(point)
+            source2()
+        }
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        let (min1, max1) = self.source1.output_range()?;
+        let (min2, max2) = self.source2.output_range()?;
+
+        Some((min1.min(min2), max1.max(max2)))
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source1
+            .sample_cost()
+            .saturating_add(self.source2.sample_cost())
+            .saturating_add(self.control.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source1.dominant_frequency(),
+            self.source2.dominant_frequency(),
+            self.control.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source1.highest_frequency(),
+            self.source2.highest_frequency(),
+            self.control.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Select{{bounds:({}, {}), falloff:{}}}({}, {}, {})",
+            self.bounds.0,
+            self.bounds.1,
+            self.falloff,
+            self.source1.describe(),
+            self.source2.describe(),
+            self.control.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+    use core::cell::Cell;
+
+    /// Source that counts how often it is sampled.
+    struct Counting {
+        value: f64,
+        samples: Cell<usize>,
+    }
+
+    impl Counting {
+        fn new(value: f64) -> Self {
+            Self {
+                value,
+                samples: Cell::new(0),
+            }
+        }
+    }
+
+    impl NoiseFn<f64, 2> for Counting {
+        fn get(&self, _point: [f64; 2]) -> f64 {
+            self.samples.set(self.samples.get() + 1);
+            self.value
+        }
+    }
+
+    #[test]
+    fn unused_branch_is_not_evaluated() {
+        let source1 = Counting::new(-1.0);
+        let source2 = Counting::new(1.0);
+
+        for &(control, samples1, samples2) in &[
+            (-0.5, 1, 0),
+            (0.5, 0, 1),
+            (1.5, 1, 0),
+            // Within the falloff band around the lower bound.
+            (0.05, 1, 1),
+        ] {
+            source1.samples.set(0);
+            source2.samples.set(0);
+
+            let select = Select::new(&source1, &source2, Constant::new(control))
+                .set_bounds(0.0, 1.0)
+                .set_falloff(0.1);
+            select.get([0.0, 0.0]);
+
+            assert_eq!(samples1, source1.samples.get(), "control {}", control);
+            assert_eq!(samples2, source2.samples.get(), "control {}", control);
         }
     }
 }
@@ -1,7 +1,13 @@
-pub use self::{displace::*, rotate_point::*, scale_point::*, translate_point::*, turbulence::*};
+pub use self::{
+    displace::*, fold_domain::*, input_jitter::*, rotate_point::*, scale_point::*,
+    translate_point::*, turbulence::*, warp_domain::*,
+};
 
 mod displace;
+mod fold_domain;
+mod input_jitter;
 mod rotate_point;
 mod scale_point;
 mod translate_point;
 mod turbulence;
+mod warp_domain;
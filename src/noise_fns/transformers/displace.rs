@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{max_frequency, NoiseFn};
 
 /// Noise function that uses multiple source functions to displace each coordinate
 /// of the input value before returning the output value from the `source` function.
@@ -62,6 +62,39 @@ where
         // original input value.
         self.source.get([x, y])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.x_displace.sample_cost())
+            .saturating_add(self.y_displace.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_displace.dominant_frequency(),
+            self.y_displace.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_displace.highest_frequency(),
+            self.y_displace.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<f64, 3>
@@ -85,6 +118,42 @@ where
         // original input value.
         self.source.get([x, y, z])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.x_displace.sample_cost())
+            .saturating_add(self.y_displace.sample_cost())
+            .saturating_add(self.z_displace.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_displace.dominant_frequency(),
+            self.y_displace.dominant_frequency(),
+            self.z_displace.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_displace.highest_frequency(),
+            self.y_displace.highest_frequency(),
+            self.z_displace.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<f64, 4>
@@ -110,4 +179,43 @@ where
         // original input value.
         self.source.get([x, y, z, u])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.x_displace.sample_cost())
+            .saturating_add(self.y_displace.sample_cost())
+            .saturating_add(self.z_displace.sample_cost())
+            .saturating_add(self.u_displace.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_displace.dominant_frequency(),
+            self.y_displace.dominant_frequency(),
+            self.z_displace.dominant_frequency(),
+            self.u_displace.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_displace.highest_frequency(),
+            self.y_displace.highest_frequency(),
+            self.z_displace.highest_frequency(),
+            self.u_displace.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
 }
@@ -0,0 +1,214 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that folds the coordinates of the input value with a
+/// triangle wave before returning the output value from the source function.
+///
+/// Each coordinate is mirrored at every multiple of its fold period, so the
+/// output of the source function is reflected across regularly spaced fold
+/// lines. This produces mirror-symmetric, kaleidoscopic tiling, as opposed to
+/// wrapping, which repeats the source function without mirroring it.
+///
+/// Fold periods must be positive.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct FoldDomain<Source> {
+    /// Source function that outputs a value
+    pub source: Source,
+
+    /// Distance between the fold lines along the _x_ axis. The default period
+    /// is set to 1.0.
+    pub x_period: f64,
+
+    /// Distance between the fold lines along the _y_ axis. The default period
+    /// is set to 1.0.
+    pub y_period: f64,
+
+    /// Distance between the fold lines along the _z_ axis. The default period
+    /// is set to 1.0.
+    pub z_period: f64,
+
+    /// Distance between the fold lines along the _u_ axis. The default period
+    /// is set to 1.0.
+    pub u_period: f64,
+}
+
+impl<Source> FoldDomain<Source> {
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            x_period: 1.0,
+            y_period: 1.0,
+            z_period: 1.0,
+            u_period: 1.0,
+        }
+    }
+
+    /// Sets the fold period along the _x_ axis.
+    pub fn set_x_period(self, x_period: f64) -> Self {
+        Self { x_period, ..self }
+    }
+
+    /// Sets the fold period along the _y_ axis.
+    pub fn set_y_period(self, y_period: f64) -> Self {
+        Self { y_period, ..self }
+    }
+
+    /// Sets the fold period along the _z_ axis.
+    pub fn set_z_period(self, z_period: f64) -> Self {
+        Self { z_period, ..self }
+    }
+
+    /// Sets the fold period along the _u_ axis.
+    pub fn set_u_period(self, u_period: f64) -> Self {
+        Self { u_period, ..self }
+    }
+
+    /// Sets the fold period along all axes.
+    pub fn set_period(self, period: f64) -> Self {
+        Self {
+            x_period: period,
+            y_period: period,
+            z_period: period,
+            u_period: period,
+            ..self
+        }
+    }
+
+    /// Sets the individual fold periods along each axis.
+    pub fn set_all_periods(
+        self,
+        x_period: f64,
+        y_period: f64,
+        z_period: f64,
+        u_period: f64,
+    ) -> Self {
+        Self {
+            x_period,
+            y_period,
+            z_period,
+            u_period,
+            ..self
+        }
+    }
+}
+
+/// Maps `value` onto a triangle wave that rises from 0 to `period` and falls
+/// back to 0 over every interval of `2 * period`.
+#[inline]
+fn fold(value: f64, period: f64) -> f64 {
+    let t = value.rem_euclid(2.0 * period);
+
+    if t > period {
+        2.0 * period - t
+    } else {
+        t
+    }
+}
+
+impl<Source> NoiseFn<f64, 2> for FoldDomain<Source>
+where
+    Source: NoiseFn<f64, 2>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source
+            .get([fold(point[0], self.x_period), fold(point[1], self.y_period)])
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+impl<Source> NoiseFn<f64, 3> for FoldDomain<Source>
+where
+    Source: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.source.get([
+            fold(point[0], self.x_period),
+            fold(point[1], self.y_period),
+            fold(point[2], self.z_period),
+        ])
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+impl<Source> NoiseFn<f64, 4> for FoldDomain<Source>
+where
+    Source: NoiseFn<f64, 4>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.source.get([
+            fold(point[0], self.x_period),
+            fold(point[1], self.y_period),
+            fold(point[2], self.z_period),
+            fold(point[3], self.u_period),
+        ])
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn output_is_symmetric_about_fold_lines() {
+        let fold = FoldDomain::new(Perlin::new(3)).set_all_periods(1.5, 2.25, 1.0, 1.0);
+
+        for k in -3..3 {
+            let x_line = k as f64 * 1.5;
+            let y_line = k as f64 * 2.25;
+
+            for d in [0.1, 0.35, 0.8, 1.2] {
+                let y = 0.6 + d;
+                assert!((fold.get([x_line + d, y]) - fold.get([x_line - d, y])).abs() < 1e-12);
+
+                let x = 0.4 + d;
+                assert!((fold.get([x, y_line + d]) - fold.get([x, y_line - d])).abs() < 1e-12);
+            }
+        }
+    }
+}
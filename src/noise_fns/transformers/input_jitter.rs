@@ -0,0 +1,131 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that offsets the input value by a tiny, deterministic
+/// amount before returning the output value from the source function.
+///
+/// When the output of a smooth source function is quantized, such as when it
+/// is written to an 8-bit image, nearly flat regions turn into visible
+/// terraces. Offsetting each sample by a different pseudo-random amount
+/// dithers the output at the source, which breaks the terraces up into noise.
+/// The offset along each axis lies within `[-amplitude, amplitude]` and is
+/// derived by hashing the input value, so the same input value always
+/// produces exactly the same output.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct InputJitter<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Largest offset applied along each axis, in input units. The default is
+    /// 0.01, a small fraction of the lattice spacing of the generators.
+    pub amplitude: f64,
+}
+
+impl<Source> InputJitter<Source> {
+    pub const DEFAULT_AMPLITUDE: f64 = 0.01;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            amplitude: Self::DEFAULT_AMPLITUDE,
+        }
+    }
+
+    pub fn set_amplitude(self, amplitude: f64) -> Self {
+        Self { amplitude, ..self }
+    }
+}
+
+/// Hashes the bits of `point` together with `axis` into a value within
+/// `[-1, 1)`.
+fn hash_point<const DIM: usize>(point: &[f64; DIM], axis: usize) -> f64 {
+    // Finalizer of the SplitMix64 generator, which mixes every input bit into
+    // every output bit.
+    fn mix(mut value: u64) -> u64 {
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+
+    let mut hash = mix(axis as u64 + 1);
+    for value in point {
+        hash = mix(hash ^ value.to_bits());
+    }
+
+    (hash >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for InputJitter<Source>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        if self.amplitude == 0.0 {
+            return self.source.get(point);
+        }
+
+        let mut jittered = point;
+        for (axis, value) in jittered.iter_mut().enumerate() {
+            *value += hash_point(&point, axis) * self.amplitude;
+        }
+
+        self.source.get(jittered)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        self.source.dominant_frequency()
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        self.source.highest_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, ScalePoint};
+
+    /// Number of horizontally adjacent samples that quantize to the same
+    /// 8-bit value.
+    fn plateau_pairs<Source: NoiseFn<f64, 2>>(source: &Source) -> usize {
+        let quantize = |x: usize, y: usize| (source.get([x as f64, y as f64]) * 127.5) as i32;
+
+        (0..64)
+            .flat_map(|y| (0..63).map(move |x| (x, y)))
+            .filter(|&(x, y)| quantize(x, y) == quantize(x + 1, y))
+            .count()
+    }
+
+    #[test]
+    fn zero_amplitude_is_identity() {
+        let source = Perlin::new(1);
+        let jitter = InputJitter::new(source).set_amplitude(0.0);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.173, i as f64 * -0.311];
+            assert_eq!(source.get(point), jitter.get(point));
+        }
+    }
+
+    #[test]
+    fn jitter_breaks_up_plateaus() {
+        // The lattice of the scaled source is 500 units wide, so the map is
+        // nearly flat and the jitter is still a small fraction of a cell.
+        let flat = ScalePoint::new(Perlin::new(1)).set_scale(0.002);
+        let jittered = InputJitter::new(flat.clone()).set_amplitude(4.0);
+
+        let before = plateau_pairs(&flat);
+        let after = plateau_pairs(&jittered);
+
+        assert!(after * 2 < before, "before: {}, after: {}", before, after);
+        assert_eq!(jittered.get([3.0, 4.0]), jittered.get([3.0, 4.0]));
+    }
+}
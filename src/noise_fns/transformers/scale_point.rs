@@ -1,4 +1,7 @@
-use crate::noise_fns::NoiseFn;
+use crate::{
+    math::{interpolate, s_curve::cubic::Cubic},
+    noise_fns::NoiseFn,
+};
 
 /// Noise function that scales the coordinates of the input value before
 /// returning the output value from the source function.
@@ -25,6 +28,17 @@ pub struct ScalePoint<Source> {
     /// Scaling factor applied to the _u_ coordinate of the input value. The
     /// default scaling factor is set to 1.0.
     pub u_scale: f64,
+
+    /// Lower and upper corners of the box of input space in which the scaling
+    /// is applied, or `None` to scale everywhere. Axes beyond the dimension of
+    /// the region are unbounded.
+    #[serde(default)]
+    pub region: Option<([f64; 4], [f64; 4])>,
+
+    /// Width of the band just inside the region over which the output at the
+    /// scaled coordinates fades in. The default falloff is 0.0.
+    #[serde(default)]
+    pub region_falloff: f64,
 }
 
 impl<Source> ScalePoint<Source> {
@@ -35,6 +49,8 @@ impl<Source> ScalePoint<Source> {
             y_scale: 1.0,
             z_scale: 1.0,
             u_scale: 1.0,
+            region: None,
+            region_falloff: 0.0,
         }
     }
 
@@ -84,6 +100,85 @@ impl<Source> ScalePoint<Source> {
             ..self
         }
     }
+
+    /// Restricts the scaling to the box of input space between `min` and
+    /// `max`, for localized zones of finer or coarser detail. Outside the box,
+    /// the source function is sampled at the original coordinates. Axes that
+    /// are not given are unbounded.
+    pub fn set_region<const DIM: usize>(self, min: [f64; DIM], max: [f64; DIM]) -> Self {
+        assert!(DIM <= 4, "ScalePoint supports at most four dimensions");
+
+        let mut lower = [f64::NEG_INFINITY; 4];
+        let mut upper = [f64::INFINITY; 4];
+        lower[..DIM].copy_from_slice(&min);
+        upper[..DIM].copy_from_slice(&max);
+
+        Self {
+            region: Some((lower, upper)),
+            ..self
+        }
+    }
+
+    /// Sets the width of the band just inside the region over which the
+    /// scaling fades in, which avoids a seam at the edges of the region.
+    pub fn set_region_falloff(self, region_falloff: f64) -> Self {
+        Self {
+            region_falloff,
+            ..self
+        }
+    }
+
+    /// Returns how strongly the scaling applies at the input value, from 0.0
+    /// outside the region to 1.0 inside it.
+    fn region_weight(&self, point: &[f64]) -> f64 {
+        let (lower, upper) = match &self.region {
+            Some(region) => region,
+            None => return 1.0,
+        };
+
+        // Distance from the input value to the nearest face of the box,
+        // negative outside the box.
+        let inside = point
+            .iter()
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|(value, (lower, upper))| (value - lower).min(upper - value))
+            .fold(f64::INFINITY, f64::min);
+
+        if self.region_falloff > 0.0 {
+            (inside / self.region_falloff).map_cubic()
+        } else if inside >= 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Blends the output at the scaled coordinates into the output at the
+    /// original coordinates by the region weight. Blending the outputs rather
+    /// than the scaling factors keeps the falloff band free of the extra
+    /// detail that a varying scale produces far from the origin.
+    fn blend<Scaled, Unscaled>(weight: f64, scaled: Scaled, unscaled: Unscaled) -> f64
+    where
+        Scaled: FnOnce() -> f64,
+        Unscaled: FnOnce() -> f64,
+    {
+        if weight >= 1.0 {
+            scaled()
+        } else if weight <= 0.0 {
+            unscaled()
+        } else {
+            interpolate::linear(unscaled(), scaled(), weight)
+        }
+    }
+
+    /// Returns the largest scaling factor that is applied somewhere.
+    fn max_scale(&self, scale: f64) -> f64 {
+        if self.region.is_some() {
+            scale.max(1.0)
+        } else {
+            scale
+        }
+    }
 }
 
 impl<Source> NoiseFn<f64, 2> for ScalePoint<Source>
@@ -91,8 +186,43 @@ where
     Source: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
+        Self::blend(
+            self.region_weight(&point),
+            || {
+                self.source
+                    .get([point[0] * self.x_scale, point[1] * self.y_scale])
+            },
+            || self.source.get(point),
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The largest scale factor compresses the source the most.
+        let scale = self.x_scale.abs().max(self.y_scale.abs());
+
+        let scale = self.max_scale(scale);
+
+        self.source
+            .dominant_frequency()
+            .map(|frequency| frequency * scale)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        let scale = self.x_scale.abs().max(self.y_scale.abs());
+
+        let scale = self.max_scale(scale);
+
         self.source
-            .get([point[0] * self.x_scale, point[1] * self.y_scale])
+            .highest_frequency()
+            .map(|frequency| frequency * scale)
     }
 }
 
@@ -101,11 +231,54 @@ where
     Source: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
-        self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
-        ])
+        Self::blend(
+            self.region_weight(&point),
+            || {
+                self.source.get([
+                    point[0] * self.x_scale,
+                    point[1] * self.y_scale,
+                    point[2] * self.z_scale,
+                ])
+            },
+            || self.source.get(point),
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The largest scale factor compresses the source the most.
+        let scale = self
+            .x_scale
+            .abs()
+            .max(self.y_scale.abs())
+            .max(self.z_scale.abs());
+
+        let scale = self.max_scale(scale);
+
+        self.source
+            .dominant_frequency()
+            .map(|frequency| frequency * scale)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        let scale = self
+            .x_scale
+            .abs()
+            .max(self.y_scale.abs())
+            .max(self.z_scale.abs());
+
+        let scale = self.max_scale(scale);
+
+        self.source
+            .highest_frequency()
+            .map(|frequency| frequency * scale)
     }
 }
 
@@ -114,11 +287,117 @@ where
     Source: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
-            point[3] * self.u_scale,
-        ])
+        Self::blend(
+            self.region_weight(&point),
+            || {
+                self.source.get([
+                    point[0] * self.x_scale,
+                    point[1] * self.y_scale,
+                    point[2] * self.z_scale,
+                    point[3] * self.u_scale,
+                ])
+            },
+            || self.source.get(point),
+        )
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source.sample_cost()
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        // The largest scale factor compresses the source the most.
+        let scale = self
+            .x_scale
+            .abs()
+            .max(self.y_scale.abs())
+            .max(self.z_scale.abs())
+            .max(self.u_scale.abs());
+
+        let scale = self.max_scale(scale);
+
+        self.source
+            .dominant_frequency()
+            .map(|frequency| frequency * scale)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        let scale = self
+            .x_scale
+            .abs()
+            .max(self.y_scale.abs())
+            .max(self.z_scale.abs())
+            .max(self.u_scale.abs());
+
+        let scale = self.max_scale(scale);
+
+        self.source
+            .highest_frequency()
+            .map(|frequency| frequency * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn region_limits_scaling() {
+        let perlin = Perlin::new(4);
+        let scale_point = ScalePoint::new(perlin)
+            .set_scale(4.0)
+            .set_region([-2.0, -2.0], [2.0, 2.0])
+            .set_region_falloff(0.5);
+
+        for i in 0..50 {
+            let t = i as f64 * 0.06 - 1.5;
+
+            // Well inside the region, past the falloff band.
+            let inside = [t, t * 0.5];
+            assert_eq!(perlin.get([t * 4.0, t * 2.0]), scale_point.get(inside));
+
+            // Well outside the region.
+            let outside = [t + 5.0, t * 3.0];
+            assert_eq!(perlin.get(outside), scale_point.get(outside));
+        }
+
+        // The scaling fades in continuously across the edge.
+        let step = 1e-4;
+        for i in 0..100 {
+            let x = 1.45 + i as f64 * 0.001;
+            let difference = scale_point.get([x, 0.3]) - scale_point.get([x + step, 0.3]);
+            assert!(difference.abs() < 0.01, "jump at {}", x);
+        }
+    }
+
+    #[test]
+    fn falloff_blends_outputs_far_from_origin() {
+        let perlin = Perlin::new(4);
+        let scale_point = ScalePoint::new(perlin)
+            .set_scale(4.0)
+            .set_region([100.0, -2.0], [104.0, 2.0])
+            .set_region_falloff(1.0);
+
+        // Across the falloff band, the output is a blend of the output at the
+        // scaled and at the original coordinates, so it varies no faster than
+        // the scaled source does.
+        let step = 1e-4;
+        for i in 0..1000 {
+            let point = [100.0 + i as f64 * 0.001, 0.3];
+            let scaled = perlin.get([point[0] * 4.0, point[1] * 4.0]);
+            let unscaled = perlin.get(point);
+
+            let value = scale_point.get(point);
+            assert!(value >= scaled.min(unscaled) - 1e-12);
+            assert!(value <= scaled.max(unscaled) + 1e-12);
+
+            let difference = value - scale_point.get([point[0] + step, point[1]]);
+            assert!(difference.abs() < 0.01, "jump at {}", point[0]);
+        }
     }
 }
@@ -1,4 +1,6 @@
-use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
+use crate::noise_fns::{max_frequency, Constant, Fbm, MultiFractal, NoiseFn, Seedable};
+use alloc::{format, string::String};
+use core::marker::PhantomData;
 
 /// Noise function that randomly displaces the input value before returning the
 /// output value from the source function.
@@ -8,11 +10,18 @@ use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
 /// retrieving the output value from the source function. To control the
 /// turbulence, an application can modify its frequency, its power, and its
 /// roughness.
+///
+/// By default the displacement along each axis is generated by an internal
+/// `Fbm<F>`. A different displacement function can be supplied with
+/// `Turbulence::with_displacement`, which allows a warp field that has already
+/// been configured to be shared between several sources, or cheaper noise to be
+/// substituted. The frequency, roughness and seed only apply to the default
+/// displacement.
+///
+/// The strength of the displacement can be varied across space with
+/// `Turbulence::set_strength_mask`, so that only some regions are warped.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
-pub struct Turbulence<Source, F>
-where
-    F: Default + Seedable,
-{
+pub struct Turbulence<Source, F, Displacement = Fbm<F>, Mask = Constant> {
     /// Source function that outputs a value.
     pub source: Source,
 
@@ -27,21 +36,74 @@ where
     pub roughness: usize,
 
     seed: u32,
-    x_distort_function: Fbm<F>,
-    y_distort_function: Fbm<F>,
-    z_distort_function: Fbm<F>,
-    u_distort_function: Fbm<F>,
+    x_distort_function: Displacement,
+    y_distort_function: Displacement,
+    z_distort_function: Displacement,
+    u_distort_function: Displacement,
+    strength_mask: Option<Mask>,
+    phantom: PhantomData<F>,
 }
 
-impl<Source, F> Turbulence<Source, F>
-where
-    F: Default + Seedable,
-{
+impl<Source, F, Displacement, Mask> Turbulence<Source, F, Displacement, Mask> {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_POWER: f64 = 1.0;
     pub const DEFAULT_ROUGHNESS: usize = 3;
 
+    pub fn set_power(self, power: f64) -> Self {
+        Self { power, ..self }
+    }
+
+    /// Scales the displacement at each point by the output value of `mask`,
+    /// remapped from `[-1, 1]` to `[0, 1]` and clamped.
+    ///
+    /// Where the mask outputs -1.0 the source function is returned unwarped,
+    /// and where it outputs 1.0 the full displacement is applied.
+    pub fn set_strength_mask<M>(self, mask: M) -> Turbulence<Source, F, Displacement, M> {
+        Turbulence {
+            source: self.source,
+            frequency: self.frequency,
+            power: self.power,
+            roughness: self.roughness,
+            seed: self.seed,
+            x_distort_function: self.x_distort_function,
+            y_distort_function: self.y_distort_function,
+            z_distort_function: self.z_distort_function,
+            u_distort_function: self.u_distort_function,
+            strength_mask: Some(mask),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn strength_mask(&self) -> Option<&Mask> {
+        self.strength_mask.as_ref()
+    }
+
+    /// Returns the factor that the displacement is scaled by at `point`.
+    fn strength<const DIM: usize>(&self, point: [f64; DIM]) -> f64
+    where
+        Mask: NoiseFn<f64, DIM>,
+    {
+        match &self.strength_mask {
+            Some(mask) => ((mask.get(point) + 1.0) * 0.5).clamp(0.0, 1.0),
+            None => 1.0,
+        }
+    }
+
+    fn mask_cost<const DIM: usize>(&self) -> u32
+    where
+        Mask: NoiseFn<f64, DIM>,
+    {
+        self.strength_mask
+            .as_ref()
+            .map_or(0, |mask| mask.sample_cost())
+    }
+}
+
+impl<Source, F> Turbulence<Source, F>
+where
+    F: Default + Seedable,
+{
     pub fn new(source: Source) -> Self {
         Self {
             source,
@@ -65,9 +127,16 @@ where
                 .set_seed(Self::DEFAULT_SEED + 3)
                 .set_octaves(Self::DEFAULT_ROUGHNESS)
                 .set_frequency(Self::DEFAULT_FREQUENCY),
+            strength_mask: None,
+            phantom: PhantomData,
         }
     }
+}
 
+impl<Source, F, Mask> Turbulence<Source, F, Fbm<F>, Mask>
+where
+    F: Default + Seedable,
+{
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self {
             frequency,
@@ -79,10 +148,6 @@ where
         }
     }
 
-    pub fn set_power(self, power: f64) -> Self {
-        Self { power, ..self }
-    }
-
     pub fn set_roughness(self, roughness: usize) -> Self {
         Self {
             roughness,
@@ -95,7 +160,35 @@ where
     }
 }
 
-impl<Source, F> Seedable for Turbulence<Source, F>
+impl<Source, Displacement> Turbulence<Source, (), Displacement>
+where
+    Displacement: Clone,
+{
+    /// Creates a turbulence function whose displacement along each axis is
+    /// generated by `displacement`, sampled at a different offset for each
+    /// axis.
+    ///
+    /// The `F` type parameter is not used by a custom displacement, and is set
+    /// to `()`. To share a displacement function between several turbulence
+    /// functions without copying it, pass it by reference or as an `Arc`.
+    pub fn with_displacement(source: Source, displacement: Displacement) -> Self {
+        Self {
+            source,
+            seed: Self::DEFAULT_SEED,
+            frequency: Self::DEFAULT_FREQUENCY,
+            power: Self::DEFAULT_POWER,
+            roughness: Self::DEFAULT_ROUGHNESS,
+            x_distort_function: displacement.clone(),
+            y_distort_function: displacement.clone(),
+            z_distort_function: displacement.clone(),
+            u_distort_function: displacement,
+            strength_mask: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Source, F, Mask> Seedable for Turbulence<Source, F, Fbm<F>, Mask>
 where
     F: Default + Seedable,
 {
@@ -115,12 +208,18 @@ where
     }
 }
 
-impl<Source, F> NoiseFn<f64, 2> for Turbulence<Source, F>
+impl<Source, F, Displacement, Mask> NoiseFn<f64, 2> for Turbulence<Source, F, Displacement, Mask>
 where
     Source: NoiseFn<f64, 2>,
-    F: Default + Seedable + NoiseFn<f64, 2>,
+    Displacement: NoiseFn<f64, 2>,
+    Mask: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
+        let power = self.power * self.strength(point);
+        if power == 0.0 {
+            return self.source.get(point);
+        }
+
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -130,19 +229,67 @@ where
         let x1 = point[0] + 26519.0 / 65536.0;
         let y1 = point[1] + 18128.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1]) * self.power);
+        let x_distort = point[0] + (self.x_distort_function.get([x0, y0]) * power);
+        let y_distort = point[1] + (self.y_distort_function.get([x1, y1]) * power);
 
         self.source.get([x_distort, y_distort])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.mask_cost())
+            .saturating_add(self.x_distort_function.sample_cost())
+            .saturating_add(self.y_distort_function.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_distort_function.dominant_frequency(),
+            self.y_distort_function.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_distort_function.highest_frequency(),
+            self.y_distort_function.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Turbulence{{power:{}}}({})",
+            self.power,
+            self.source.describe()
+        )
+    }
 }
 
-impl<Source, F> NoiseFn<f64, 3> for Turbulence<Source, F>
+impl<Source, F, Displacement, Mask> NoiseFn<f64, 3> for Turbulence<Source, F, Displacement, Mask>
 where
     Source: NoiseFn<f64, 3>,
-    F: Default + Seedable + NoiseFn<f64, 3>,
+    Displacement: NoiseFn<f64, 3>,
+    Mask: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
+        let power = self.power * self.strength(point);
+        if power == 0.0 {
+            return self.source.get(point);
+        }
+
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -158,20 +305,71 @@ where
         let y2 = point[1] + 11213.0 / 65536.0;
         let z2 = point[2] + 44845.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2]) * self.power);
+        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0]) * power);
+        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1]) * power);
+        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2]) * power);
 
         self.source.get([x_distort, y_distort, z_distort])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.mask_cost())
+            .saturating_add(self.x_distort_function.sample_cost())
+            .saturating_add(self.y_distort_function.sample_cost())
+            .saturating_add(self.z_distort_function.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_distort_function.dominant_frequency(),
+            self.y_distort_function.dominant_frequency(),
+            self.z_distort_function.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_distort_function.highest_frequency(),
+            self.y_distort_function.highest_frequency(),
+            self.z_distort_function.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Turbulence{{power:{}}}({})",
+            self.power,
+            self.source.describe()
+        )
+    }
 }
 
-impl<Source, F> NoiseFn<f64, 4> for Turbulence<Source, F>
+impl<Source, F, Displacement, Mask> NoiseFn<f64, 4> for Turbulence<Source, F, Displacement, Mask>
 where
     Source: NoiseFn<f64, 4>,
-    F: Default + Seedable + NoiseFn<f64, 4>,
+    Displacement: NoiseFn<f64, 4>,
+    Mask: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
+        let power = self.power * self.strength(point);
+        if power == 0.0 {
+            return self.source.get(point);
+        }
+
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -195,12 +393,110 @@ where
         let z3 = point[2] + 12414.0 / 65536.0;
         let u3 = point[3] + 60943.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0, u0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1, u1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2, u2]) * self.power);
-        let u_distort = point[3] + (self.u_distort_function.get([x3, y3, z3, u3]) * self.power);
+        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0, u0]) * power);
+        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1, u1]) * power);
+        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2, u2]) * power);
+        let u_distort = point[3] + (self.u_distort_function.get([x3, y3, z3, u3]) * power);
 
         self.source
             .get([x_distort, y_distort, z_distort, u_distort])
     }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        self.source
+            .sample_cost()
+            .saturating_add(self.mask_cost())
+            .saturating_add(self.x_distort_function.sample_cost())
+            .saturating_add(self.y_distort_function.sample_cost())
+            .saturating_add(self.z_distort_function.sample_cost())
+            .saturating_add(self.u_distort_function.sample_cost())
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        [
+            self.source.dominant_frequency(),
+            self.x_distort_function.dominant_frequency(),
+            self.y_distort_function.dominant_frequency(),
+            self.z_distort_function.dominant_frequency(),
+            self.u_distort_function.dominant_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        [
+            self.source.highest_frequency(),
+            self.x_distort_function.highest_frequency(),
+            self.y_distort_function.highest_frequency(),
+            self.z_distort_function.highest_frequency(),
+            self.u_distort_function.highest_frequency(),
+        ]
+        .iter()
+        .copied()
+        .fold(None, max_frequency)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Turbulence{{power:{}}}({})",
+            self.power,
+            self.source.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn constant_displacement_translates_source() {
+        let source = Perlin::new(4);
+        let turbulence = Turbulence::with_displacement(source, Constant::new(0.5)).set_power(0.25);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.19, i as f64 * -0.37, 0.6];
+            let translated = [point[0] + 0.125, point[1] + 0.125, point[2] + 0.125];
+            assert_eq!(source.get(translated), turbulence.get(point));
+        }
+    }
+
+    #[test]
+    fn shared_displacement_matches_default() {
+        let source = Perlin::new(4);
+        let default = Turbulence::<_, Perlin>::new(source);
+        let displacement = Fbm::<Perlin>::default()
+            .set_octaves(Turbulence::<Perlin, Perlin>::DEFAULT_ROUGHNESS)
+            .set_seed(0)
+            .set_frequency(1.0);
+        let shared = Turbulence::with_displacement(source, &displacement);
+
+        // Only the x axis uses the same seed in both configurations.
+        let point = [0.3, 0.0];
+        let x_distort = 0.3 + displacement.get([0.3 + 12414.0 / 65536.0, 65124.0 / 65536.0]);
+        let y_distort = displacement.get([0.3 + 26519.0 / 65536.0, 18128.0 / 65536.0]);
+        assert_eq!(source.get([x_distort, y_distort]), shared.get(point));
+        assert_ne!(default.get(point), shared.get(point));
+    }
+
+    #[test]
+    fn strength_mask_scales_displacement() {
+        let source = Perlin::new(4);
+        let turbulence = Turbulence::<_, Perlin>::new(source).set_power(0.5);
+        let unwarped = turbulence.clone().set_strength_mask(Constant::new(-1.0));
+        let full = turbulence.clone().set_strength_mask(Constant::new(1.0));
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.19, i as f64 * -0.37, 0.6];
+            assert_eq!(source.get(point), unwarped.get(point));
+            assert_eq!(turbulence.get(point), full.get(point));
+        }
+    }
 }
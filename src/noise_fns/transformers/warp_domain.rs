@@ -0,0 +1,127 @@
+use crate::noise_fns::{max_frequency, NoiseFn};
+
+/// Noise function that warps the 2-dimensional input value with a vector
+/// field before returning the output value from the source function.
+///
+/// The `warp` function is sampled twice, at two fixed offsets, to build an
+/// offset vector `(dx, dy)`. That vector is scaled by `strength` and added to
+/// the input value before sampling the `source` function:
+///
+/// ```text
+/// q = (warp(p + offset_x), warp(p + offset_y))
+/// output = source(p + strength * q)
+/// ```
+///
+/// This is the domain warping technique popularized by Inigo Quilez. Using a
+/// fractal as the `warp` function produces organic, swirling distortions.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WarpDomain2<Source, Warp> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Function used to build the warp vector.
+    pub warp: Warp,
+
+    /// Scales the warp vector before it is added to the input value. The
+    /// default strength is set to 1.0.
+    pub strength: f64,
+
+    /// Offset at which `warp` is sampled to build the _x_ component of the
+    /// warp vector.
+    pub x_offset: [f64; 2],
+
+    /// Offset at which `warp` is sampled to build the _y_ component of the
+    /// warp vector.
+    pub y_offset: [f64; 2],
+}
+
+impl<Source, Warp> WarpDomain2<Source, Warp> {
+    pub const DEFAULT_STRENGTH: f64 = 1.0;
+    pub const DEFAULT_X_OFFSET: [f64; 2] = [0.0, 0.0];
+    pub const DEFAULT_Y_OFFSET: [f64; 2] = [5.2, 1.3];
+
+    pub fn new(source: Source, warp: Warp) -> Self {
+        Self {
+            source,
+            warp,
+            strength: Self::DEFAULT_STRENGTH,
+            x_offset: Self::DEFAULT_X_OFFSET,
+            y_offset: Self::DEFAULT_Y_OFFSET,
+        }
+    }
+
+    pub fn set_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+
+    /// Sets the offsets at which `warp` is sampled for the _x_ and _y_
+    /// components of the warp vector. The two offsets should be far enough
+    /// apart that the components are uncorrelated.
+    pub fn set_offsets(self, x_offset: [f64; 2], y_offset: [f64; 2]) -> Self {
+        Self {
+            x_offset,
+            y_offset,
+            ..self
+        }
+    }
+}
+
+impl<Source, Warp> NoiseFn<f64, 2> for WarpDomain2<Source, Warp>
+where
+    Source: NoiseFn<f64, 2>,
+    Warp: NoiseFn<f64, 2>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let dx = self
+            .warp
+            .get([point[0] + self.x_offset[0], point[1] + self.x_offset[1]]);
+        let dy = self
+            .warp
+            .get([point[0] + self.y_offset[0], point[1] + self.y_offset[1]]);
+
+        self.source
+            .get([point[0] + dx * self.strength, point[1] + dy * self.strength])
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+
+    fn sample_cost(&self) -> u32 {
+        // The warp function is sampled once per axis.
+        self.source
+            .sample_cost()
+            .saturating_add(self.warp.sample_cost().saturating_mul(2))
+    }
+
+    fn dominant_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source.dominant_frequency(),
+            self.warp.dominant_frequency(),
+        )
+    }
+
+    fn highest_frequency(&self) -> Option<f64> {
+        max_frequency(
+            self.source.highest_frequency(),
+            self.warp.highest_frequency(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, Perlin};
+
+    #[test]
+    fn zero_strength_is_unwarped() {
+        let source = Perlin::new(1);
+        let warp = WarpDomain2::new(source, Fbm::<Perlin>::new(2)).set_strength(0.0);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.17, i as f64 * -0.23];
+            assert_eq!(source.get(point), warp.get(point));
+        }
+    }
+}
@@ -14,6 +14,26 @@ const TABLE_SIZE: usize = 256;
 
 pub trait NoiseHasher: Send + Sync {
     fn hash(&self, to_hash: &[isize]) -> usize;
+
+    /// Hashes each coordinate tuple in `coords` into the matching element of
+    /// `out`, giving the same results as calling `hash` on each tuple.
+    ///
+    /// Hashers can override this to process a whole batch of lattice points
+    /// at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coords` and `out` have different lengths.
+    fn hash_many<const DIM: usize>(&self, coords: &[[isize; DIM]], out: &mut [usize])
+    where
+        Self: Sized,
+    {
+        assert_eq!(coords.len(), out.len());
+
+        for (coord, out) in coords.iter().zip(out) {
+            *out = self.hash(coord);
+        }
+    }
 }
 
 /// A seed table, required by all noise functions.
@@ -101,6 +121,9 @@ impl PermutationTable {
     ///
     /// Internally this uses a `XorShiftRng`, but we don't really need to worry
     /// about cryptographic security when working with procedural noise.
+    ///
+    /// The output for a given seed is stable, and is pinned by the reference
+    /// tables in `tests/data/permutation_tables.txt`.
     pub fn new(seed: u32) -> Self {
         let mut real = [0; 16];
         real[0] = 1;
@@ -113,6 +136,54 @@ impl PermutationTable {
         let mut rng: XorShiftRng = SeedableRng::from_seed(real);
         rng.gen()
     }
+
+    /// Deterministically generates a new permutation table from a name, such
+    /// as the name of a world.
+    ///
+    /// This is equivalent to `PermutationTable::new(seed_from_str(name))`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Self {
+        Self::new(seed_from_str(name))
+    }
+}
+
+/// Hashes a string into a `u32` seed using 32-bit FNV-1a.
+///
+/// The hash is computed over the UTF-8 bytes of the string, so the seed for a
+/// given string is the same on every platform, and will not change between
+/// versions of this crate.
+pub fn seed_from_str(name: &str) -> u32 {
+    const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const FNV_PRIME: u32 = 0x0100_0193;
+
+    name.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Derives the seed of the child at `index` from a master seed.
+///
+/// The seeds are scrambled with the 32-bit MurmurHash3 finalizer, so the
+/// children of one master seed, and the children of neighbouring master
+/// seeds, receive seeds that share no obvious pattern. Like
+/// [`seed_from_str`], the derived seeds will not change between versions of
+/// this crate.
+///
+/// The `from_seed` constructors of the two-source combinators, such as
+/// [`Add::from_seed`](crate::Add::from_seed), give their first source the
+/// child at index 0 and their second source the child at index 1. Two sources
+/// of the same kind then never share a seed, so they do not produce
+/// correlated output.
+pub fn derive_seed(master: u32, index: u32) -> u32 {
+    let mut hash = master ^ index.wrapping_add(1).wrapping_mul(0x9e37_79b9);
+
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+
+    hash
 }
 
 impl NoiseHasher for PermutationTable {
@@ -124,6 +195,65 @@ impl NoiseHasher for PermutationTable {
             .unwrap();
         self.values[index] as usize
     }
+
+    fn hash_many<const DIM: usize>(&self, coords: &[[isize; DIM]], out: &mut [usize]) {
+        assert!(DIM > 0, "cannot hash an empty coordinate tuple");
+        assert_eq!(coords.len(), out.len());
+
+        // With the tuple length known at compile time, the fold over each
+        // tuple unrolls into a chain of table lookups, and the table stays hot
+        // in the cache for the whole batch.
+        let values = &self.values;
+        for (coord, out) in coords.iter().zip(out) {
+            let index = coord[1..].iter().fold((coord[0] & 0xff) as usize, |a, &b| {
+                values[a] as usize ^ (b & 0xff) as usize
+            });
+            *out = values[index] as usize;
+        }
+    }
+}
+
+/// A hasher that wraps every lattice coordinate into `[0, period)` before
+/// passing it on to the inner hasher.
+///
+/// Any lattice noise built on top of this hasher repeats every `period` cells
+/// along each axis, which makes it tile seamlessly.
+#[derive(Clone, Copy, Debug)]
+pub struct PeriodicHasher<'a, NH>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    hasher: &'a NH,
+    period: isize,
+}
+
+impl<'a, NH> PeriodicHasher<'a, NH>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    /// Wraps `hasher` so that it repeats every `period` cells. `period` must
+    /// be greater than zero.
+    pub fn new(hasher: &'a NH, period: usize) -> Self {
+        assert!(period > 0, "period must be greater than zero");
+
+        Self {
+            hasher,
+            period: period as isize,
+        }
+    }
+}
+
+impl<'a, NH> NoiseHasher for PeriodicHasher<'a, NH>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        let mut wrapped = [0; 4];
+        for (w, &a) in wrapped.iter_mut().zip(to_hash) {
+            *w = a.rem_euclid(self.period);
+        }
+        self.hasher.hash(&wrapped[..to_hash.len()])
+    }
 }
 
 impl fmt::Debug for PermutationTable {
@@ -132,11 +262,186 @@ impl fmt::Debug for PermutationTable {
     }
 }
 
+/// A thread-safe cache of recently used permutation tables, keyed by seed.
+///
+/// Generating a table shuffles 256 bytes, which adds up when many chunks are
+/// regenerated with their own derived seeds. The pool keeps the most recently
+/// used tables around (evicting the least recently used one once `capacity`
+/// is reached), and always returns the same table as `PermutationTable::new`
+/// for a given seed. The pool can be shared between worker threads by
+/// reference.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PermutationTablePool {
+    capacity: usize,
+    tables: std::sync::Mutex<alloc::collections::VecDeque<(u32, PermutationTable)>>,
+}
+
+#[cfg(feature = "std")]
+impl PermutationTablePool {
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tables: std::sync::Mutex::new(alloc::collections::VecDeque::with_capacity(
+                capacity.max(1),
+            )),
+        }
+    }
+
+    /// Returns the table for `seed`, generating and caching it if it is not
+    /// already in the pool.
+    pub fn get(&self, seed: u32) -> PermutationTable {
+        let mut tables = self
+            .tables
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(index) = tables.iter().position(|&(s, _)| s == seed) {
+            let entry = tables.remove(index).unwrap();
+            tables.push_front(entry);
+            return entry.1;
+        }
+
+        let table = PermutationTable::new(seed);
+        if tables.len() >= self.capacity {
+            tables.pop_back();
+        }
+        tables.push_front((seed, table));
+
+        table
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of tables currently cached.
+    pub fn len(&self) -> usize {
+        self.tables
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for PermutationTablePool {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{seed_from_str, NoiseHasher, PeriodicHasher, PermutationTable, TABLE_SIZE};
     use crate::{NoiseFn, Perlin, Seedable};
+    use alloc::vec::Vec;
     use rand::random;
 
+    #[test]
+    fn test_reference_tables() {
+        let vectors = include_str!("../tests/data/permutation_tables.txt");
+
+        let mut count = 0;
+        for line in vectors.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (seed, values) = line.split_once(':').expect("missing seed separator");
+            let seed: u32 = seed.trim().parse().expect("invalid seed");
+            let values: Vec<u8> = values
+                .split_whitespace()
+                .map(|value| value.parse().expect("invalid table value"))
+                .collect();
+
+            assert_eq!(TABLE_SIZE, values.len(), "seed {}", seed);
+            assert_eq!(
+                &values[..],
+                &PermutationTable::new(seed).values[..],
+                "seed {}",
+                seed
+            );
+            count += 1;
+        }
+
+        assert!(count > 0, "no reference tables found");
+    }
+
+    #[test]
+    fn test_hash_many_matches_hash() {
+        let table = PermutationTable::new(11);
+        let coords: Vec<[isize; 3]> = (-20..20)
+            .map(|i| [i * 7, -i * 13 + 300, i * i - 1000])
+            .collect();
+
+        let mut out = vec![0; coords.len()];
+        table.hash_many(&coords, &mut out);
+        for (coord, &hash) in coords.iter().zip(&out) {
+            assert_eq!(table.hash(coord), hash);
+        }
+
+        // The default implementation, used by hashers without an override.
+        let periodic = PeriodicHasher::new(&table, 5);
+        periodic.hash_many(&coords, &mut out);
+        for (coord, &hash) in coords.iter().zip(&out) {
+            assert_eq!(periodic.hash(coord), hash);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pool_matches_new() {
+        let pool = super::PermutationTablePool::new(2);
+
+        for seed in [0, 1, 2, 0, 3, 1, 1] {
+            assert_eq!(PermutationTable::new(seed).values, pool.get(seed).values);
+        }
+        assert_eq!(2, pool.len());
+
+        let pool = std::sync::Arc::new(pool);
+        let handles: Vec<_> = (0..4)
+            .map(|seed| {
+                let pool = pool.clone();
+                std::thread::spawn(move || pool.get(seed).values)
+            })
+            .collect();
+        for (seed, handle) in handles.into_iter().enumerate() {
+            assert_eq!(
+                PermutationTable::new(seed as u32).values,
+                handle.join().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_seed_from_str() {
+        // Published FNV-1a test vectors; these must never change.
+        assert_eq!(0x811c_9dc5, seed_from_str(""));
+        assert_eq!(0xe40c_292c, seed_from_str("a"));
+        assert_eq!(0xbf9c_f968, seed_from_str("foobar"));
+
+        assert_eq!(seed_from_str("Atlantis"), seed_from_str("Atlantis"));
+        assert_ne!(seed_from_str("Atlantis"), seed_from_str("Atlantic"));
+        assert_ne!(seed_from_str("world1"), seed_from_str("world2"));
+
+        assert_eq!(
+            PermutationTable::new(seed_from_str("Atlantis")).values,
+            PermutationTable::from_str("Atlantis").values
+        );
+        assert_eq!(
+            seed_from_str("Atlantis"),
+            Perlin::default().set_seed_str("Atlantis").seed()
+        );
+    }
+
     #[test]
     fn test_random_seed() {
         let perlin = Perlin::default().set_seed(random());
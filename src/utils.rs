@@ -1,10 +1,23 @@
 #[cfg(feature = "image")]
 pub use self::image_renderer::*;
-pub use self::{color_gradient::*, noise_image::*, noise_map::*, noise_map_builder::*};
+pub use self::{
+    baked_noise::*, color_gradient::*, lazy_noise_map::*, looping::*, noise_image::*, noise_map::*,
+    noise_map_builder::*, noise_map_source::*, path::*, rgb_noise::*, surface_sampler::*,
+    texture_set::*, volume::*,
+};
 
+mod baked_noise;
 mod color_gradient;
 #[cfg(feature = "image")]
 mod image_renderer;
+mod lazy_noise_map;
+mod looping;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod noise_map_source;
+mod path;
+mod rgb_noise;
+mod surface_sampler;
+mod texture_set;
+mod volume;
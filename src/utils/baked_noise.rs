@@ -0,0 +1,268 @@
+use crate::{
+    error::NoiseError,
+    noise_fns::NoiseFn,
+    utils::{
+        noise_map::{EdgeMode, NoiseMap},
+        noise_map_builder::{Bounds, NoiseMapBuilder, PlaneMapBuilder},
+        noise_map_source::NoiseMapSource,
+    },
+};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+/// Identifies a blob written by [`bake_to_blob`].
+const MAGIC: [u8; 4] = *b"NBAK";
+
+/// Version of the blob layout.
+const VERSION: u8 = 1;
+
+/// Length of the header: the magic, the version, the bit depth, the width and
+/// height as `u32`, and the bounds and value range as `f64`.
+const HEADER_LEN: usize = 4 + 1 + 1 + 2 * 4 + 6 * 8;
+
+/// Samples `source` on a grid over `bounds` and packs the quantized values into
+/// a compact binary blob, which [`BakedNoise::from_blob`] loads again.
+///
+/// The grid has `resolution.0` by `resolution.1` samples, positioned the same
+/// way [`PlaneMapBuilder`] positions the samples of a map over `bounds.0`
+/// along _x_ and `bounds.1` along _y_. The values are mapped linearly from
+/// their range onto integers of `bits` bits, so every value is stored to
+/// within half a step of `(max - min) / (2^bits - 1)`. The blob holds a small
+/// header followed by the tightly packed values, and all numbers are stored
+/// little-endian.
+///
+/// # Panics
+///
+/// Panics if `bits` is not between 1 and 32.
+pub fn bake_to_blob<Source>(
+    source: &Source,
+    bounds: (Bounds, Bounds),
+    resolution: (usize, usize),
+    bits: u8,
+) -> Vec<u8>
+where
+    Source: NoiseFn<f64, 2> + ?Sized,
+{
+    assert!(
+        (1..=32).contains(&bits),
+        "bit depth must be between 1 and 32"
+    );
+
+    let (width, height) = resolution;
+    let map = PlaneMapBuilder::<_, 2>::new(source)
+        .set_size(width, height)
+        .set_bounds(bounds.0, bounds.1)
+        .set_track_range(true)
+        .build();
+    let (min, max) = map.value_range().unwrap_or((0.0, 0.0));
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + (width * height * bits as usize + 7) / 8);
+    blob.extend_from_slice(&MAGIC);
+    blob.push(VERSION);
+    blob.push(bits);
+    blob.extend_from_slice(&(width as u32).to_le_bytes());
+    blob.extend_from_slice(&(height as u32).to_le_bytes());
+    for value in [
+        bounds.0.min(),
+        bounds.0.max(),
+        bounds.1.min(),
+        bounds.1.max(),
+        min,
+        max,
+    ] {
+        blob.extend_from_slice(&value.to_le_bytes());
+    }
+
+    // Pack the values least significant bit first.
+    let levels = max_level(bits) as f64;
+    let mut buffer = 0_u64;
+    let mut buffered = 0;
+    for &value in map.iter() {
+        let level = if max > min {
+            ((value - min) / (max - min) * levels).round() as u64
+        } else {
+            0
+        };
+
+        buffer |= level << buffered;
+        buffered += bits as u32;
+        while buffered >= 8 {
+            blob.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    }
+    if buffered > 0 {
+        blob.push(buffer as u8);
+    }
+
+    blob
+}
+
+/// Returns the largest integer stored with `bits` bits.
+fn max_level(bits: u8) -> u32 {
+    u32::MAX >> (32 - bits as u32)
+}
+
+/// A noise function that resamples a blob baked by [`bake_to_blob`].
+///
+/// Loading a blob unpacks its values once, after which sampling costs a
+/// bilinear interpolation between the four nearest grid points, whatever the
+/// cost of the baked source. Grid points return the quantized source values,
+/// and positions outside the baked bounds are clamped to its edges unless
+/// another [`EdgeMode`] is set.
+#[derive(Debug, Clone)]
+pub struct BakedNoise {
+    source: NoiseMapSource,
+    bits: u8,
+}
+
+impl BakedNoise {
+    /// Loads a blob written by [`bake_to_blob`], returning an error if the blob
+    /// is truncated or was not written by this crate.
+    pub fn from_blob(blob: &[u8]) -> Result<Self, NoiseError> {
+        if blob.len() < HEADER_LEN || blob[..4] != MAGIC || blob[4] != VERSION {
+            return Err(NoiseError::InvalidBlob);
+        }
+
+        let bits = blob[5];
+        if !(1..=32).contains(&bits) {
+            return Err(NoiseError::InvalidBlob);
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(blob[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        let read_f64 =
+            |offset: usize| f64::from_le_bytes(blob[offset..offset + 8].try_into().unwrap());
+
+        let (width, height) = (read_u32(6), read_u32(10));
+        let x_bounds = (read_f64(14), read_f64(22));
+        let y_bounds = (read_f64(30), read_f64(38));
+        let (min, max) = (read_f64(46), read_f64(54));
+
+        let packed = &blob[HEADER_LEN..];
+        let count = width.checked_mul(height).ok_or(NoiseError::InvalidBlob)?;
+        match count.checked_mul(bits as usize) {
+            Some(packed_bits) if packed.len() == (packed_bits + 7) / 8 => {}
+            _ => return Err(NoiseError::InvalidBlob),
+        }
+
+        // Unpack the values least significant bit first.
+        let mask = max_level(bits) as u64;
+        let step = (max - min) / mask as f64;
+        let mut map = NoiseMap::new(width, height);
+        let mut bytes = packed.iter();
+        let mut buffer = 0_u64;
+        let mut buffered = 0;
+        for value in map.iter_mut() {
+            while buffered < bits as u32 {
+                buffer |= (*bytes.next().unwrap() as u64) << buffered;
+                buffered += 8;
+            }
+
+            *value = min + (buffer & mask) as f64 * step;
+            buffer >>= bits;
+            buffered -= bits as u32;
+        }
+
+        Ok(Self {
+            source: NoiseMapSource::new(map, x_bounds, y_bounds),
+            bits,
+        })
+    }
+
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self {
+            source: self.source.set_edge_mode(edge_mode),
+            ..self
+        }
+    }
+
+    /// Returns the number of bits each value was quantized to.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.source.map().size()
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.source.x_bounds()
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.source.y_bounds()
+    }
+}
+
+impl NoiseFn<f64, 2> for BakedNoise {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source.get(point)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        self.source.output_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, Perlin};
+
+    #[test]
+    fn baked_grid_points_match_source() {
+        let fbm = Fbm::<Perlin>::new(4);
+        let bounds = (
+            Bounds::new(-1.0, 3.0).unwrap(),
+            Bounds::new(0.5, 2.5).unwrap(),
+        );
+
+        let (min, max) = PlaneMapBuilder::<_, 2>::new(&fbm)
+            .set_size(40, 20)
+            .set_bounds(bounds.0, bounds.1)
+            .set_track_range(true)
+            .build()
+            .value_range()
+            .unwrap();
+
+        for &bits in &[1, 4, 8, 13, 16, 32] {
+            let blob = bake_to_blob(&fbm, bounds, (40, 20), bits);
+            let baked = BakedNoise::from_blob(&blob).unwrap();
+
+            assert_eq!(bits, baked.bits());
+            assert_eq!((40, 20), baked.size());
+            assert_eq!((-1.0, 3.0), baked.x_bounds());
+
+            let tolerance = (max - min) / max_level(bits) as f64 * 0.5 + 1e-9;
+            for y in 0..20 {
+                for x in 0..40 {
+                    let point = [-1.0 + 0.1 * x as f64, 0.5 + 0.1 * y as f64];
+                    let error = (fbm.get(point) - baked.get(point)).abs();
+                    assert!(error <= tolerance, "{} > {}", error, tolerance);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn damaged_blobs_are_rejected() {
+        let bounds = (
+            Bounds::new(0.0, 1.0).unwrap(),
+            Bounds::new(0.0, 1.0).unwrap(),
+        );
+        let blob = bake_to_blob(&Perlin::new(1), bounds, (5, 3), 7);
+        assert!(BakedNoise::from_blob(&blob).is_ok());
+
+        assert_eq!(
+            Err(NoiseError::InvalidBlob),
+            BakedNoise::from_blob(&blob[..blob.len() - 1]).map(|_| ())
+        );
+        assert_eq!(
+            Err(NoiseError::InvalidBlob),
+            BakedNoise::from_blob(&blob[1..]).map(|_| ())
+        );
+    }
+}
@@ -2,6 +2,30 @@ use alloc::vec::Vec;
 
 pub type Color = [u8; 4];
 
+/// Named color gradients covering the common ways of rendering noise maps.
+///
+/// Each preset spans the domain `[-1, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientPreset {
+    /// Deep ocean below -256/16384, shallow water up to 0.0, then grass, dirt,
+    /// rock and snow from 0.0 up to 1.0.
+    Terrain,
+
+    /// Black at -1.0 to white at 1.0.
+    Grayscale,
+
+    /// The "jet" colormap: dark blue at -1.0, blue at -0.75, cyan at -0.25,
+    /// yellow at 0.25, red at 0.75 and dark red at 1.0.
+    Jet,
+
+    /// Deep water at -1.0 through shallow water up to 0.0, a narrow beach up to
+    /// 0.05, then lowland green fading to hills at 1.0.
+    Ocean,
+
+    /// Dark sand in the hollows at -1.0 rising to pale dune crests at 1.0.
+    Desert,
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct GradientPoint {
     pos: f64,
@@ -44,6 +68,19 @@ impl ColorGradient {
         gradient.build_grayscale_gradient()
     }
 
+    /// Creates a gradient with the control points of the given preset.
+    pub fn preset(preset: GradientPreset) -> Self {
+        let gradient = Self::new();
+
+        match preset {
+            GradientPreset::Terrain => gradient.build_terrain_gradient(),
+            GradientPreset::Grayscale => gradient.build_grayscale_gradient(),
+            GradientPreset::Jet => gradient.build_jet_gradient(),
+            GradientPreset::Ocean => gradient.build_ocean_gradient(),
+            GradientPreset::Desert => gradient.build_desert_gradient(),
+        }
+    }
+
     pub fn add_gradient_point(mut self, pos: f64, color: Color) -> Self {
         let new_point = GradientPoint { pos, color };
 
@@ -124,6 +161,42 @@ impl ColorGradient {
             .add_gradient_point( 1.0, [255,   0,   0, 255])
     }
 
+    #[rustfmt::skip]
+    pub fn build_jet_gradient(self) -> Self {
+        self.clear_gradient()
+            .add_gradient_point(-1.00, [  0,   0, 128, 255])
+            .add_gradient_point(-0.75, [  0,   0, 255, 255])
+            .add_gradient_point(-0.25, [  0, 255, 255, 255])
+            .add_gradient_point( 0.25, [255, 255,   0, 255])
+            .add_gradient_point( 0.75, [255,   0,   0, 255])
+            .add_gradient_point( 1.00, [128,   0,   0, 255])
+    }
+
+    #[rustfmt::skip]
+    pub fn build_ocean_gradient(self) -> Self {
+        self.clear_gradient()
+            .add_gradient_point(-1.00, [  0,   8,  40, 255])
+            .add_gradient_point(-0.50, [  0,  30, 100, 255])
+            .add_gradient_point(-0.10, [  0,  90, 170, 255])
+            .add_gradient_point( 0.00, [ 60, 170, 210, 255])
+            .add_gradient_point( 0.02, [230, 215, 160, 255])
+            .add_gradient_point( 0.05, [210, 195, 140, 255])
+            .add_gradient_point( 0.15, [ 90, 150,  60, 255])
+            .add_gradient_point( 0.50, [ 40, 100,  35, 255])
+            .add_gradient_point( 1.00, [100,  90,  60, 255])
+    }
+
+    #[rustfmt::skip]
+    pub fn build_desert_gradient(self) -> Self {
+        self.clear_gradient()
+            .add_gradient_point(-1.0, [110,  70,  40, 255])
+            .add_gradient_point(-0.4, [170, 110,  60, 255])
+            .add_gradient_point( 0.0, [210, 160, 100, 255])
+            .add_gradient_point( 0.4, [235, 200, 140, 255])
+            .add_gradient_point( 0.8, [250, 225, 175, 255])
+            .add_gradient_point( 1.0, [255, 240, 210, 255])
+    }
+
     pub fn get_color(&self, pos: f64) -> Color {
         let mut color = Color::default();
 
@@ -190,4 +263,27 @@ mod tests {
 
         assert_eq!([127, 127, 127, 127], gradient.get_color(0.5));
     }
+
+    #[test]
+    fn presets_have_increasing_positions() {
+        for &preset in &[
+            GradientPreset::Terrain,
+            GradientPreset::Grayscale,
+            GradientPreset::Jet,
+            GradientPreset::Ocean,
+            GradientPreset::Desert,
+        ] {
+            let gradient = ColorGradient::preset(preset);
+            let positions: Vec<f64> = gradient.gradient_points.iter().map(|x| x.pos).collect();
+
+            assert!(positions.len() >= 2, "{:?}", preset);
+            assert!(
+                positions.windows(2).all(|pair| pair[0] < pair[1]),
+                "{:?}: {:?}",
+                preset,
+                positions
+            );
+            assert_eq!((-1.0, 1.0), (positions[0], positions[positions.len() - 1]));
+        }
+    }
 }
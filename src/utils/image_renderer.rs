@@ -1,4 +1,7 @@
-use crate::math::interpolate;
+use crate::{
+    math::{interpolate, signed_pow},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
 use core::{self, f64::consts::SQRT_2};
 
 use super::{color_gradient::*, noise_image::*, noise_map::*};
@@ -15,6 +18,10 @@ pub struct ImageRenderer {
 
     // Flag specifying whether wrapping is enabled.
     wrap_enabled: bool,
+
+    // Gamma exponent applied to the noise map values before they are mapped
+    // onto the color gradient.
+    gamma: f64,
 }
 
 impl ImageRenderer {
@@ -24,6 +31,7 @@ impl ImageRenderer {
             light_source: LightSource::new(),
             light_enabled: false,
             wrap_enabled: false,
+            gamma: 1.0,
         }
     }
 
@@ -118,6 +126,17 @@ impl ImageRenderer {
         self.wrap_enabled
     }
 
+    /// Sets the gamma exponent applied to each noise map value, as
+    /// `sign(x) * |x|^gamma`, before the value is mapped onto the color
+    /// gradient. Default is 1.0, which leaves the values unchanged.
+    pub fn set_gamma(self, gamma: f64) -> Self {
+        Self { gamma, ..self }
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
     pub fn render(&mut self, noise_map: &NoiseMap) -> NoiseImage {
         // noise_map.width
         let (width, height) = noise_map.size();
@@ -128,7 +147,7 @@ impl ImageRenderer {
             for x in 0..width {
                 let point = noise_map[(x, y)];
 
-                let source_color = self.gradient.get_color(point);
+                let source_color = self.gradient.get_color(signed_pow(point, self.gamma));
 
                 let mut light_intensity;
 
@@ -193,6 +212,114 @@ impl ImageRenderer {
         destination_image
     }
 
+    /// Renders three noise maps directly into the red, green and blue channels
+    /// of an image, without using the color gradient or the light source.
+    ///
+    /// The values of each map are expected to lie within `[-1, 1]`, and are
+    /// mapped onto `[0, 255]` after the gamma exponent has been applied. Values
+    /// outside that range are clamped. The resulting image is fully opaque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the three noise maps do not have the same size.
+    pub fn render_rgb(&mut self, red: &NoiseMap, green: &NoiseMap, blue: &NoiseMap) -> NoiseImage {
+        let (width, height) = red.size();
+        assert_eq!(red.size(), green.size());
+        assert_eq!(red.size(), blue.size());
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        let to_channel = |value: f64| {
+            let value = (signed_pow(value, self.gamma) + 1.0) * 0.5;
+            (value.clamp(0.0, 1.0) * 255.0) as u8
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                destination_image[(x, y)] = [
+                    to_channel(red[(x, y)]),
+                    to_channel(green[(x, y)]),
+                    to_channel(blue[(x, y)]),
+                    255,
+                ];
+            }
+        }
+
+        destination_image
+    }
+
+    /// Renders a 2-dimensional vector field as a Line Integral Convolution
+    /// (LIC) image, which shows the direction of the field as streaks.
+    ///
+    /// The field is given as two noise maps holding the _x_ and _y_ components
+    /// of the vector at each cell, such as the output of a curl noise function.
+    /// Each pixel averages a white noise texture along the streamline passing
+    /// through it, following the field for `streak_length` pixels in both
+    /// directions. The magnitude of the field is ignored. The result is mapped
+    /// onto the color gradient, and streamlines wrap around the edges of the
+    /// image if wrapping is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two noise maps do not have the same size.
+    pub fn render_flow_field(
+        &mut self,
+        x_component: &NoiseMap,
+        y_component: &NoiseMap,
+        streak_length: usize,
+    ) -> NoiseImage {
+        let (width, height) = x_component.size();
+        assert_eq!(x_component.size(), y_component.size());
+
+        let hasher = PermutationTable::new(0);
+        let white_noise =
+            |x: usize, y: usize| hasher.hash(&[x as isize, y as isize]) as f64 / 127.5 - 1.0;
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = white_noise(x, y);
+                let mut count = 1;
+
+                for &direction in &[1.0, -1.0] {
+                    let (mut px, mut py) = (x as f64 + 0.5, y as f64 + 0.5);
+
+                    for _ in 0..streak_length {
+                        let (cx, cy) = (px as usize % width, py as usize % height);
+                        let (vx, vy) = (x_component[(cx, cy)], y_component[(cx, cy)]);
+                        let magnitude = (vx * vx + vy * vy).sqrt();
+                        if magnitude == 0.0 || magnitude.is_nan() {
+                            break;
+                        }
+
+                        px += direction * vx / magnitude;
+                        py += direction * vy / magnitude;
+
+                        if self.wrap_enabled {
+                            px = px.rem_euclid(width as f64);
+                            py = py.rem_euclid(height as f64);
+                        } else if px < 0.0 || py < 0.0 || px >= width as f64 || py >= height as f64
+                        {
+                            break;
+                        }
+
+                        sum += white_noise(px as usize % width, py as usize % height);
+                        count += 1;
+                    }
+                }
+
+                // Averaging independent samples shrinks their spread by the
+                // square root of the sample count, so stretch it back out.
+                let value = sum / (count as f64).sqrt();
+
+                destination_image[(x, y)] = self.gradient.get_color(signed_pow(value, self.gamma));
+            }
+        }
+
+        destination_image
+    }
+
     fn calc_destination_color(&self, source_color: Color, light_value: f64) -> Color {
         let source = u8_array_to_f64_array(source_color);
 
@@ -239,7 +366,7 @@ impl ImageRenderer {
         for y in 0..height {
             for x in 0..width {
                 let point = noise_map[(x, y)];
-                let source_color = self.gradient.get_color(point);
+                let source_color = self.gradient.get_color(signed_pow(point, self.gamma));
 
                 let mut light_intensity;
 
@@ -503,4 +630,40 @@ mod tests {
         assert_eq!([0.0; 4], u8_array_to_f64_array([0; 4]));
         assert_eq!([1.0; 4], u8_array_to_f64_array([255; 4]));
     }
+
+    #[test]
+    fn rgb_channels_render_independently() {
+        let mut red = NoiseMap::new(2, 1);
+        let mut green = NoiseMap::new(2, 1);
+        let mut blue = NoiseMap::new(2, 1);
+        red[(0, 0)] = -1.0;
+        red[(1, 0)] = 1.0;
+        green[(0, 0)] = 1.0;
+        blue[(1, 0)] = 2.0;
+
+        let image = ImageRenderer::new().render_rgb(&red, &green, &blue);
+
+        assert_eq!([0, 255, 127, 255], image[(0, 0)]);
+        assert_eq!([255, 127, 255, 255], image[(1, 0)]);
+    }
+
+    #[test]
+    fn constant_flow_field_renders_aligned_streaks() {
+        let mut x_component = NoiseMap::new(64, 64);
+        let y_component = NoiseMap::new(64, 64);
+        x_component.iter_mut().for_each(|value| *value = 0.5);
+
+        let image = ImageRenderer::new().render_flow_field(&x_component, &y_component, 8);
+
+        let (mut along, mut across) = (0.0, 0.0);
+        for y in 0..63 {
+            for x in 0..63 {
+                let value = f64::from(image[(x, y)][0]);
+                along += (value - f64::from(image[(x + 1, y)][0])).abs();
+                across += (value - f64::from(image[(x, y + 1)][0])).abs();
+            }
+        }
+
+        assert!(along * 3.0 < across, "along: {}, across: {}", along, across);
+    }
 }
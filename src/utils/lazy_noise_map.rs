@@ -0,0 +1,134 @@
+use crate::noise_fns::NoiseFn;
+
+/// A read-only view of a planar noise map that computes its values on demand.
+///
+/// `LazyNoiseMap` stores the source function and bounds instead of a backing
+/// buffer, so it can describe arbitrarily large virtual extents. Values are
+/// sampled at the same positions that [`PlaneMapBuilder`] uses, so
+/// `get_value` returns the same result as the corresponding entry of an
+/// eagerly built [`NoiseMap`].
+///
+/// Since there is no buffer to write into, `LazyNoiseMap` has no `set_value`;
+/// use [`PlaneMapBuilder`] to build a mutable [`NoiseMap`] instead.
+///
+/// [`PlaneMapBuilder`]: crate::utils::PlaneMapBuilder
+/// [`NoiseMap`]: crate::utils::NoiseMap
+#[derive(Debug, Clone)]
+pub struct LazyNoiseMap<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 2>,
+{
+    size: (usize, usize),
+    border_value: f64,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    source_module: SourceModule,
+}
+
+impl<SourceModule> LazyNoiseMap<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 2>,
+{
+    pub fn new(source_module: SourceModule, width: usize, height: usize) -> Self {
+        Self {
+            size: (width, height),
+            border_value: 0.0,
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        Self {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn set_border_value(self, border_value: f64) -> Self {
+        Self {
+            border_value,
+            ..self
+        }
+    }
+
+    pub fn border_value(&self) -> f64 {
+        self.border_value
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    /// Computes the value at the given position, or returns the border value
+    /// if the position is outside of the map.
+    pub fn get_value(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        if x < width && y < height {
+            let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+            let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
+
+            let current_x = self.x_bounds.0 + x_step * x as f64;
+            let current_y = self.y_bounds.0 + y_step * y as f64;
+
+            self.source_module.get([current_x, current_y])
+        } else {
+            self.border_value
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        utils::{NoiseMapBuilder, PlaneMapBuilder},
+        Perlin,
+    };
+
+    #[test]
+    fn lazy_values_match_built_map() {
+        let perlin = Perlin::new(11);
+
+        let lazy = LazyNoiseMap::new(perlin, 37, 23)
+            .set_x_bounds(-3.0, 2.0)
+            .set_y_bounds(0.5, 4.0);
+        let map = PlaneMapBuilder::<_, 2>::new(perlin)
+            .set_size(37, 23)
+            .set_x_bounds(-3.0, 2.0)
+            .set_y_bounds(0.5, 4.0)
+            .build();
+
+        for y in 0..23 {
+            for x in 0..37 {
+                assert_eq!(map.get_value(x, y), lazy.get_value(x, y));
+            }
+        }
+
+        assert_eq!(map.get_value(37, 0), lazy.get_value(37, 0));
+    }
+}
@@ -0,0 +1,51 @@
+use core::f64::consts::PI;
+
+/// Maps a 2D point and a time fraction onto a 4D point for seamlessly looping
+/// animations.
+///
+/// The _x_ and _y_ coordinates are passed through unchanged, while `t` is
+/// mapped onto a circle of the given `radius` in the _z_-_w_ plane, with each
+/// whole turn of the circle corresponding to one unit of `t`. Sampling a 4D
+/// noise function at the returned point while `t` runs from 0.0 to 1.0
+/// therefore produces an animation whose last frame flows back into its first.
+///
+/// The radius controls how quickly the noise changes over the loop: a larger
+/// radius travels further through the noise function, which produces more
+/// change per loop.
+pub fn looping_point(x: f64, y: f64, t: f64, radius: f64) -> [f64; 4] {
+    // Wrap t into [0, 1) so that whole turns map onto exactly the same point.
+    let angle = t.rem_euclid(1.0) * 2.0 * PI;
+
+    [x, y, radius * angle.cos(), radius * angle.sin()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoiseFn, Perlin};
+
+    #[test]
+    fn loop_start_and_end_coincide() {
+        assert_eq!(
+            looping_point(0.3, -1.2, 0.0, 2.0),
+            looping_point(0.3, -1.2, 1.0, 2.0)
+        );
+        assert_eq!(
+            looping_point(0.3, -1.2, 0.25, 2.0),
+            looping_point(0.3, -1.2, 2.25, 2.0)
+        );
+    }
+
+    #[test]
+    fn time_moves_along_circle() {
+        let perlin = Perlin::new(0);
+        let point = looping_point(0.5, 0.5, 0.25, 1.5);
+
+        assert!(point[2].abs() < 1e-12);
+        assert!((point[3] - 1.5).abs() < 1e-12);
+        assert_ne!(
+            perlin.get(looping_point(0.5, 0.5, 0.0, 1.5)),
+            perlin.get(looping_point(0.5, 0.5, 0.1, 1.5))
+        );
+    }
+}
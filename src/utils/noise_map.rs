@@ -3,16 +3,72 @@ use alloc::{
     vec::{IntoIter, Vec},
 };
 use core::ops::{Index, IndexMut};
-#[cfg(feature = "images")]
+#[cfg(feature = "std")]
 use std::path::Path;
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
+
+/// Policy for reading positions that lie outside of a [`NoiseMap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Positions outside the map read the nearest value on its edge. This
+    /// suits maps with a border that should not bleed into the other side.
+    Clamp,
+
+    /// Positions outside the map wrap around to the opposite edge, so the map
+    /// repeats. This suits maps built to be tileable.
+    Wrap,
+
+    /// Positions outside the map are reflected back into it, repeating the
+    /// edge value once, so the map repeats as alternating mirror images.
+    Mirror,
+}
+
+impl EdgeMode {
+    /// Maps `index` onto a valid index into an axis of `size` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn resolve(self, index: isize, size: usize) -> usize {
+        assert!(size > 0, "cannot resolve an index into an empty axis");
+
+        let size = size as isize;
+        let resolved = match self {
+            EdgeMode::Clamp => index.clamp(0, size - 1),
+            EdgeMode::Wrap => index.rem_euclid(size),
+            EdgeMode::Mirror => {
+                let index = index.rem_euclid(2 * size);
+                if index < size {
+                    index
+                } else {
+                    2 * size - 1 - index
+                }
+            }
+        };
+
+        resolved as usize
+    }
+}
+
+/// Byte order of the values in a raw heightmap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first, as expected by most engines on x86 and
+    /// ARM, including Unity and Unreal.
+    Little,
+
+    /// Most significant byte first.
+    Big,
+}
+
 #[derive(Debug, Clone)]
 pub struct NoiseMap {
     size: (usize, usize),
     border_value: f64,
     map: Vec<f64>,
+    value_range: Option<(f64, f64)>,
 }
 
 impl NoiseMap {
@@ -25,6 +81,7 @@ impl NoiseMap {
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, f64> {
+        self.value_range = None;
         self.map.iter_mut()
     }
 
@@ -46,12 +103,14 @@ impl NoiseMap {
                 Self {
                     map: vec![0.0; map_size],
                     size: (width, height),
+                    value_range: None,
                     ..self
                 }
             } else {
                 // Vec capacity is already big enough, so leave it alone and just change the set size.
                 Self {
                     size: (width, height),
+                    value_range: None,
                     ..self
                 }
             }
@@ -75,6 +134,7 @@ impl NoiseMap {
 
     pub fn set_value(&mut self, x: usize, y: usize, value: f64) {
         let (width, height) = self.size;
+        self.value_range = None;
 
         if x < width && y < height {
             self.map[x + y * width] = value;
@@ -93,6 +153,192 @@ impl NoiseMap {
         }
     }
 
+    /// Returns the `(min, max)` range of the values, if it was recorded when
+    /// the map was built by a builder with range tracking enabled.
+    ///
+    /// This allows the map to be normalized without scanning it again. The
+    /// range is cleared when the values of the map are modified.
+    pub fn value_range(&self) -> Option<(f64, f64)> {
+        self.value_range
+    }
+
+    pub(crate) fn set_value_range(&mut self, value_range: Option<(f64, f64)>) {
+        self.value_range = value_range;
+    }
+
+    /// Returns the value at the given position, resolving positions outside the
+    /// map with `edge_mode` instead of returning the border value.
+    ///
+    /// Returns the border value if the map is empty.
+    pub fn get_value_with_edge_mode(&self, x: isize, y: isize, edge_mode: EdgeMode) -> f64 {
+        let (width, height) = self.size;
+        if width == 0 || height == 0 {
+            return self.border_value;
+        }
+
+        self.map[edge_mode.resolve(x, width) + edge_mode.resolve(y, height) * width]
+    }
+
+    /// Returns a copy of the map reduced in size by `factor` along each axis.
+    ///
+    /// Each value of the new map is the mean of the corresponding
+    /// `factor`x`factor` block of the original map. Unlike taking every
+    /// `factor`th value, this preserves the mean of the map and reduces
+    /// aliasing, which makes it suitable for building mip pyramids. If the size
+    /// of the map is not a multiple of `factor`, the blocks along the right and
+    /// bottom edges are averaged over the values they cover.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero.
+    pub fn downsample(&self, factor: usize) -> Self {
+        assert!(factor > 0, "downsample factor must be positive");
+
+        let (width, height) = self.size;
+        let new_width = width / factor + usize::from(width % factor != 0);
+        let new_height = height / factor + usize::from(height % factor != 0);
+
+        let mut result = Self::new(new_width, new_height).set_border_value(self.border_value);
+
+        for y in 0..new_height {
+            let y_range = (y * factor)..((y + 1) * factor).min(height);
+
+            for x in 0..new_width {
+                let x_range = (x * factor)..((x + 1) * factor).min(width);
+
+                let mut sum = 0.0;
+                for source_y in y_range.clone() {
+                    for source_x in x_range.clone() {
+                        sum += self[(source_x, source_y)];
+                    }
+                }
+
+                result[(x, y)] = sum / (x_range.len() * y_range.len()) as f64;
+            }
+        }
+
+        result
+    }
+
+    /// Returns a copy of the map reduced in size by `factor` along each axis,
+    /// like [`downsample`](Self::downsample), except that the blocks along
+    /// the right and bottom edges always average `factor`x`factor` values,
+    /// reading the values past the edges of the map with `edge_mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero.
+    pub fn downsample_with_edge_mode(&self, factor: usize, edge_mode: EdgeMode) -> Self {
+        assert!(factor > 0, "downsample factor must be positive");
+
+        let (width, height) = self.size;
+        let new_width = width / factor + usize::from(width % factor != 0);
+        let new_height = height / factor + usize::from(height % factor != 0);
+
+        let mut result = Self::new(new_width, new_height).set_border_value(self.border_value);
+
+        for y in 0..new_height {
+            for x in 0..new_width {
+                let mut sum = 0.0;
+                for source_y in (y * factor)..((y + 1) * factor) {
+                    for source_x in (x * factor)..((x + 1) * factor) {
+                        sum += self.get_value_with_edge_mode(
+                            source_x as isize,
+                            source_y as isize,
+                            edge_mode,
+                        );
+                    }
+                }
+
+                result[(x, y)] = sum / (factor * factor) as f64;
+            }
+        }
+
+        result
+    }
+
+    /// Returns the values as a headerless raw heightmap of unsigned 16-bit
+    /// integers, in rows from the top of the map.
+    ///
+    /// The range recorded with [`value_range`](Self::value_range), or -1.0 to
+    /// 1.0 if there is none, is mapped linearly onto the full range of the
+    /// integers. Values outside the range are clamped.
+    pub fn to_raw_u16(&self, endianness: Endianness) -> Vec<u8> {
+        self.to_raw(|t| {
+            let value = (t * u16::MAX as f64).round() as u16;
+            match endianness {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes(),
+            }
+        })
+    }
+
+    /// Returns the values as a headerless raw heightmap of signed 16-bit
+    /// integers, like [`to_raw_u16`](Self::to_raw_u16), with the bottom of the
+    /// range mapped onto `i16::MIN`.
+    pub fn to_raw_i16(&self, endianness: Endianness) -> Vec<u8> {
+        self.to_raw(|t| {
+            let value = ((t * u16::MAX as f64).round() + i16::MIN as f64) as i16;
+            match endianness {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes(),
+            }
+        })
+    }
+
+    /// Returns the values as a headerless raw heightmap of signed 32-bit
+    /// integers, like [`to_raw_u16`](Self::to_raw_u16), with the bottom of the
+    /// range mapped onto `i32::MIN`.
+    pub fn to_raw_i32(&self, endianness: Endianness) -> Vec<u8> {
+        self.to_raw(|t| {
+            let value = ((t * u32::MAX as f64).round() + i32::MIN as f64) as i32;
+            match endianness {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes(),
+            }
+        })
+    }
+
+    /// Maps every value onto `[0, 1]` and collects the bytes that `encode`
+    /// returns for it.
+    fn to_raw<const BYTES: usize>(&self, encode: impl Fn(f64) -> [u8; BYTES]) -> Vec<u8> {
+        let (min, max) = self.value_range.unwrap_or((-1.0, 1.0));
+        let extent = max - min;
+
+        let mut bytes = Vec::with_capacity(self.map.len() * BYTES);
+        for &value in &self.map {
+            let t = if extent > 0.0 {
+                ((value - min) / extent).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            bytes.extend_from_slice(&encode(t));
+        }
+
+        bytes
+    }
+
+    /// Writes the values to a headerless raw heightmap of unsigned 16-bit
+    /// integers, as returned by [`to_raw_u16`](Self::to_raw_u16).
+    #[cfg(feature = "std")]
+    pub fn write_raw_u16(&self, filename: &Path, endianness: Endianness) -> std::io::Result<()> {
+        std::fs::write(filename, self.to_raw_u16(endianness))
+    }
+
+    /// Writes the values to a headerless raw heightmap of signed 16-bit
+    /// integers, as returned by [`to_raw_i16`](Self::to_raw_i16).
+    #[cfg(feature = "std")]
+    pub fn write_raw_i16(&self, filename: &Path, endianness: Endianness) -> std::io::Result<()> {
+        std::fs::write(filename, self.to_raw_i16(endianness))
+    }
+
+    /// Writes the values to a headerless raw heightmap of signed 32-bit
+    /// integers, as returned by [`to_raw_i32`](Self::to_raw_i32).
+    #[cfg(feature = "std")]
+    pub fn write_raw_i32(&self, filename: &Path, endianness: Endianness) -> std::io::Result<()> {
+        std::fs::write(filename, self.to_raw_i32(endianness))
+    }
+
     #[cfg(feature = "images")]
     pub fn write_to_file(&self, filename: &Path) {
         // collect the values from f64 into u8 in a separate vec
@@ -119,6 +365,7 @@ impl NoiseMap {
             size: (0, 0),
             border_value: 0.0,
             map: Vec::new(),
+            value_range: None,
         }
     }
 }
@@ -145,6 +392,7 @@ impl Index<(usize, usize)> for NoiseMap {
 impl IndexMut<(usize, usize)> for NoiseMap {
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
         let (width, height) = self.size;
+        self.value_range = None;
         if x < width && y < height {
             &mut self.map[x + y * width]
         } else {
@@ -185,3 +433,109 @@ impl<'a> IntoIterator for &'a mut NoiseMap {
         self.iter_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsample_preserves_constant_map() {
+        let mut map = NoiseMap::new(9, 7);
+        map.iter_mut().for_each(|value| *value = 0.375);
+
+        let small = map.downsample(3);
+        assert_eq!((3, 3), small.size());
+        for y in 0..3 {
+            for x in 0..3 {
+                assert_eq!(0.375, small[(x, y)]);
+            }
+        }
+    }
+
+    #[test]
+    fn downsample_preserves_mass() {
+        let mut map = NoiseMap::new(8, 6);
+        for y in 0..6 {
+            for x in 0..8 {
+                map[(x, y)] = ((x * 7 + y * 3) % 5) as f64 * 0.25 - 0.5;
+            }
+        }
+
+        let factor = 2;
+        let small = map.downsample(factor);
+        assert_eq!((4, 3), small.size());
+
+        let total: f64 = map.iter().sum();
+        let small_total: f64 = small.iter().sum();
+        assert!((total - small_total * (factor * factor) as f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn edge_modes_at_border_indices() {
+        assert_eq!(0, EdgeMode::Clamp.resolve(-1, 4));
+        assert_eq!(3, EdgeMode::Clamp.resolve(4, 4));
+        assert_eq!(3, EdgeMode::Wrap.resolve(-1, 4));
+        assert_eq!(0, EdgeMode::Wrap.resolve(4, 4));
+        assert_eq!(0, EdgeMode::Mirror.resolve(-1, 4));
+        assert_eq!(3, EdgeMode::Mirror.resolve(4, 4));
+        assert_eq!(2, EdgeMode::Mirror.resolve(5, 4));
+
+        for &mode in &[EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror] {
+            assert_eq!(0, mode.resolve(0, 4));
+            assert_eq!(3, mode.resolve(3, 4));
+        }
+    }
+
+    #[test]
+    fn downsample_with_edge_mode_fills_partial_blocks() {
+        let mut map = NoiseMap::new(3, 1);
+        map[(0, 0)] = 1.0;
+        map[(1, 0)] = 0.0;
+        map[(2, 0)] = -1.0;
+
+        let clamped = map.downsample_with_edge_mode(2, EdgeMode::Clamp);
+        let wrapped = map.downsample_with_edge_mode(2, EdgeMode::Wrap);
+        let mirrored = map.downsample_with_edge_mode(2, EdgeMode::Mirror);
+
+        assert_eq!((2, 1), clamped.size());
+        assert_eq!(-1.0, clamped[(1, 0)]);
+        assert_eq!(0.0, wrapped[(1, 0)]);
+        assert_eq!(-1.0, mirrored[(1, 0)]);
+    }
+
+    #[test]
+    fn raw_heightmaps_have_expected_bytes() {
+        let mut map = NoiseMap::new(2, 2);
+        map[(0, 0)] = -1.0;
+        map[(1, 0)] = 0.0;
+        map[(0, 1)] = 1.0;
+        map[(1, 1)] = 0.5;
+
+        assert_eq!(
+            vec![0x00, 0x00, 0x00, 0x80, 0xff, 0xff, 0xff, 0xbf],
+            map.to_raw_u16(Endianness::Little)
+        );
+        assert_eq!(
+            vec![0x00, 0x00, 0x80, 0x00, 0xff, 0xff, 0xbf, 0xff],
+            map.to_raw_u16(Endianness::Big)
+        );
+        assert_eq!(
+            vec![0x00, 0x80, 0x00, 0x00, 0xff, 0x7f, 0xff, 0x3f],
+            map.to_raw_i16(Endianness::Little)
+        );
+        assert_eq!(
+            vec![
+                0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff, 0x3f, 0xff,
+                0xff, 0xff,
+            ],
+            map.to_raw_i32(Endianness::Big)
+        );
+
+        // A recorded range is stretched over the full range of the integers.
+        map.set_value_range(Some((-1.0, 0.5)));
+        assert_eq!(
+            vec![0x00, 0x00, 0xaa, 0xaa, 0xff, 0xff, 0xff, 0xff],
+            map.to_raw_u16(Endianness::Little)
+        );
+    }
+}
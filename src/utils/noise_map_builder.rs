@@ -1,4 +1,61 @@
-use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use crate::{error::NoiseError, math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use core::convert::TryFrom;
+
+/// A validated, non-empty range of input coordinates along one axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    min: f64,
+    max: f64,
+}
+
+impl Bounds {
+    /// Creates a new range, returning an error if `min` is not strictly less
+    /// than `max` or if either bound is not finite.
+    pub fn new(min: f64, max: f64) -> Result<Self, NoiseError> {
+        if min.is_finite() && max.is_finite() && min < max {
+            Ok(Self { min, max })
+        } else {
+            Err(NoiseError::InvalidBounds { min, max })
+        }
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn extent(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Validates a range passed to one of the tuple setters. An invalid range
+    /// panics in debug builds, and is kept as given in release builds, where
+    /// the tuple setters stay unchecked as they always were.
+    fn debug_checked(min: f64, max: f64) -> (f64, f64) {
+        match Self::new(min, max) {
+            Ok(bounds) => bounds.into(),
+            Err(error) if cfg!(debug_assertions) => panic!("{}", error),
+            Err(_) => (min, max),
+        }
+    }
+}
+
+impl From<Bounds> for (f64, f64) {
+    fn from(bounds: Bounds) -> Self {
+        (bounds.min, bounds.max)
+    }
+}
+
+impl TryFrom<(f64, f64)> for Bounds {
+    type Error = NoiseError;
+
+    fn try_from((min, max): (f64, f64)) -> Result<Self, Self::Error> {
+        Self::new(min, max)
+    }
+}
 #[derive(Debug, Clone)]
 pub struct NoiseFnWrapper<SourceFn, const DIM: usize>
 where
@@ -26,6 +83,40 @@ pub trait NoiseMapBuilder<SourceModule> {
     fn build(&self) -> NoiseMap;
 }
 
+/// Calls `visit` with the coordinates of every pixel of a map of the given
+/// size.
+///
+/// Without a tile size, the pixels are visited row by row. With a tile size,
+/// the map is split into square tiles of that many pixels, visited row by row,
+/// and the pixels of each tile are in turn visited row by row before moving to
+/// the next tile. A tile size of zero is treated as one.
+///
+/// Building in tiles of, say, 64 by 64 pixels keeps consecutive samples close
+/// together in both directions, which helps source modules with large working
+/// sets stay in cache. The pixels visited are the same whatever the tile size,
+/// so the output of a map builder does not depend on it.
+pub(crate) fn for_each_pixel(
+    size: (usize, usize),
+    tile_size: Option<usize>,
+    mut visit: impl FnMut(usize, usize),
+) {
+    let (width, height) = size;
+    let (tile_width, tile_height) = match tile_size {
+        Some(tile_size) => (tile_size.max(1), tile_size.max(1)),
+        None => (width.max(1), height.max(1)),
+    };
+
+    for tile_y in (0..height).step_by(tile_height) {
+        for tile_x in (0..width).step_by(tile_width) {
+            for y in tile_y..(tile_y + tile_height).min(height) {
+                for x in tile_x..(tile_x + tile_width).min(width) {
+                    visit(x, y);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CylinderMapBuilder<SourceModule>
 where
@@ -33,6 +124,7 @@ where
 {
     angle_bounds: (f64, f64),
     height_bounds: (f64, f64),
+    tile_size: Option<usize>,
     size: (usize, usize),
     source_module: SourceModule,
 }
@@ -45,6 +137,7 @@ where
         CylinderMapBuilder {
             angle_bounds: (-90.0, 90.0),
             height_bounds: (-1.0, 1.0),
+            tile_size: None,
             size: (100, 100),
             source_module,
         }
@@ -76,6 +169,12 @@ where
         }
     }
 
+    /// Sets the size of the square tiles the map is built in, or `None` to
+    /// build it row by row, the default. Tiling never changes the output.
+    pub fn set_tile_size(self, tile_size: Option<usize>) -> Self {
+        CylinderMapBuilder { tile_size, ..self }
+    }
+
     pub fn angle_bounds(&self) -> (f64, f64) {
         self.angle_bounds
     }
@@ -83,6 +182,10 @@ where
     pub fn height_bounds(&self) -> (f64, f64) {
         self.height_bounds
     }
+
+    pub fn tile_size(&self) -> Option<usize> {
+        self.tile_size
+    }
 }
 
 impl<SourceModule> NoiseMapBuilder<SourceModule> for CylinderMapBuilder<SourceModule>
@@ -118,20 +221,17 @@ where
         let x_step = angle_extent / width as f64;
         let y_step = height_extent / height as f64;
 
-        for y in 0..height {
+        for_each_pixel(self.size, self.tile_size, |x, y| {
             let current_height = self.height_bounds.0 + y_step * y as f64;
+            let current_angle = self.angle_bounds.0 + x_step * x as f64;
 
-            for x in 0..width {
-                let current_angle = self.angle_bounds.0 + x_step * x as f64;
+            let point_x = current_angle.to_radians().cos();
+            let point_z = current_angle.to_radians().sin();
 
-                let point_x = current_angle.to_radians().cos();
-                let point_z = current_angle.to_radians().sin();
+            let value = self.source_module.get([point_x, current_height, point_z]);
 
-                let value = self.source_module.get([point_x, current_height, point_z]);
-
-                result_map[(x, y)] = value;
-            }
-        }
+            result_map[(x, y)] = value;
+        });
 
         result_map
     }
@@ -145,6 +245,9 @@ where
     is_seamless: bool,
     x_bounds: (f64, f64),
     y_bounds: (f64, f64),
+    world_grid: Option<((isize, isize), f64)>,
+    track_range: bool,
+    tile_size: Option<usize>,
     size: (usize, usize),
     source_module: SourceModule,
 }
@@ -158,6 +261,9 @@ where
             is_seamless: false,
             x_bounds: (-1.0, 1.0),
             y_bounds: (-1.0, 1.0),
+            world_grid: None,
+            track_range: false,
+            tile_size: None,
             size: (100, 100),
             source_module,
         }
@@ -170,29 +276,229 @@ where
         }
     }
 
+    /// Sets the _x_ bounds.
+    ///
+    /// The range is checked with [`Bounds::new`] in debug builds, which panic
+    /// if it is inverted, empty or not finite. Use
+    /// [`set_bounds`](Self::set_bounds) to handle invalid ranges as errors.
     pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
         PlaneMapBuilder {
-            x_bounds: (lower_x_bound, upper_x_bound),
+            x_bounds: Bounds::debug_checked(lower_x_bound, upper_x_bound),
             ..self
         }
     }
 
+    /// Sets the _y_ bounds, checked like the _x_ bounds in
+    /// [`set_x_bounds`](Self::set_x_bounds).
     pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
         PlaneMapBuilder {
-            y_bounds: (lower_y_bound, upper_y_bound),
+            y_bounds: Bounds::debug_checked(lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    /// Sets both the _x_ and _y_ bounds from validated ranges.
+    pub fn set_bounds(self, x_bounds: Bounds, y_bounds: Bounds) -> Self {
+        PlaneMapBuilder {
+            x_bounds: x_bounds.into(),
+            y_bounds: y_bounds.into(),
+            ..self
+        }
+    }
+
+    /// Samples a window of a fixed world-space grid instead of the bounds.
+    ///
+    /// Pixel `(x, y)` of the map is sampled at
+    /// `((origin.0 + x) * spacing, (origin.1 + y) * spacing)`, so the sample
+    /// positions depend only on the absolute grid index rather than on the
+    /// size of the map. Panning with a whole number of pixels therefore
+    /// reproduces the values of the overlapping region exactly, and changing
+    /// the spacing zooms around fixed world coordinates instead of stretching
+    /// the bounds by a fraction of a pixel.
+    ///
+    /// While a world grid is set, the bounds set with
+    /// [`set_x_bounds`](Self::set_x_bounds),
+    /// [`set_y_bounds`](Self::set_y_bounds) and
+    /// [`set_bounds`](Self::set_bounds) are ignored, and
+    /// [`x_bounds`](Self::x_bounds) and [`y_bounds`](Self::y_bounds) report
+    /// the area covered by the window.
+    pub fn set_world_grid(self, origin: (isize, isize), spacing: f64) -> Self {
+        PlaneMapBuilder {
+            world_grid: Some((origin, spacing)),
             ..self
         }
     }
 
+    /// Enables or disables recording the range of the values while building,
+    /// which is then available from [`NoiseMap::value_range`]. Disabled by
+    /// default.
+    pub fn set_track_range(self, track_range: bool) -> Self {
+        PlaneMapBuilder {
+            track_range,
+            ..self
+        }
+    }
+
+    /// Sets the size of the square tiles the map is built in, or `None` to
+    /// build it row by row, the default. Tiling never changes the output.
+    pub fn set_tile_size(self, tile_size: Option<usize>) -> Self {
+        PlaneMapBuilder { tile_size, ..self }
+    }
+
+    /// Returns the grid origin and spacing set with
+    /// [`set_world_grid`](Self::set_world_grid), if any.
+    pub fn world_grid(&self) -> Option<((isize, isize), f64)> {
+        self.world_grid
+    }
+
+    pub fn tile_size(&self) -> Option<usize> {
+        self.tile_size
+    }
+
     pub fn x_bounds(&self) -> (f64, f64) {
-        self.x_bounds
+        match self.world_grid {
+            Some(((origin, _), spacing)) => (
+                origin as f64 * spacing,
+                (origin + self.size.0 as isize) as f64 * spacing,
+            ),
+            None => self.x_bounds,
+        }
     }
 
     pub fn y_bounds(&self) -> (f64, f64) {
-        self.y_bounds
+        match self.world_grid {
+            Some(((_, origin), spacing)) => (
+                origin as f64 * spacing,
+                (origin + self.size.1 as isize) as f64 * spacing,
+            ),
+            None => self.y_bounds,
+        }
+    }
+
+    /// Returns whether the pixels of the map are spaced more widely than the
+    /// source module's
+    /// [`recommended_sample_spacing`](NoiseFn::recommended_sample_spacing),
+    /// in which case its finest detail aliases into noise. Increase the size of
+    /// the map or shrink its bounds to fix this.
+    pub fn is_undersampled(&self) -> bool {
+        let (x_lower, x_upper) = self.x_bounds();
+        let (y_lower, y_upper) = self.y_bounds();
+        let spacing = ((x_upper - x_lower) / self.size.0 as f64)
+            .abs()
+            .max(((y_upper - y_lower) / self.size.1 as f64).abs());
+
+        spacing > self.source_module.recommended_sample_spacing()
+    }
+
+    /// Returns the _x_ coordinate at which column `x` of the map is sampled.
+    fn sample_x(&self, x: usize) -> f64 {
+        match self.world_grid {
+            Some(((origin, _), spacing)) => (origin + x as isize) as f64 * spacing,
+            None => {
+                let x_step = (self.x_bounds.1 - self.x_bounds.0) / self.size.0 as f64;
+                self.x_bounds.0 + x_step * x as f64
+            }
+        }
+    }
+
+    /// Returns the _y_ coordinate at which row `y` of the map is sampled.
+    fn sample_y(&self, y: usize) -> f64 {
+        match self.world_grid {
+            Some(((_, origin), spacing)) => (origin + y as isize) as f64 * spacing,
+            None => {
+                let y_step = (self.y_bounds.1 - self.y_bounds.0) / self.size.1 as f64;
+                self.y_bounds.0 + y_step * y as f64
+            }
+        }
     }
 }
 
+macro_rules! impl_plane_build_into {
+    ($dim:expr $(, $extra:expr)*) => {
+        impl<SourceModule> PlaneMapBuilder<SourceModule, $dim>
+        where
+            SourceModule: NoiseFn<f64, $dim>,
+        {
+            /// Builds the noise map into an existing map, overwriting its
+            /// values in place instead of allocating a new map.
+            ///
+            /// This avoids an allocation per build when the same map is
+            /// regenerated repeatedly, such as when animating or live editing.
+            /// Returns an error if the size of the map does not match the size
+            /// of the builder.
+            pub fn build_into(&self, map: &mut NoiseMap) -> Result<(), NoiseError> {
+                if map.size() != self.size {
+                    return Err(NoiseError::SizeMismatch {
+                        expected: self.size,
+                        found: map.size(),
+                    });
+                }
+
+                self.fill(map);
+
+                Ok(())
+            }
+
+            /// Writes the samples of the builder into a map of the builder's
+            /// size.
+            fn fill(&self, map: &mut NoiseMap) {
+                let (x_bounds, y_bounds) = (self.x_bounds(), self.y_bounds());
+                let x_extent = x_bounds.1 - x_bounds.0;
+                let y_extent = y_bounds.1 - y_bounds.0;
+
+                let mut min = f64::INFINITY;
+                let mut max = f64::NEG_INFINITY;
+
+                for_each_pixel(self.size, self.tile_size, |x, y| {
+                    let current_x = self.sample_x(x);
+                    let current_y = self.sample_y(y);
+
+                    let final_value = if self.is_seamless {
+                        let sw_value =
+                            self.source_module.get([current_x, current_y $(, $extra)*]);
+                        let se_value = self
+                            .source_module
+                            .get([current_x + x_extent, current_y $(, $extra)*]);
+                        let nw_value = self
+                            .source_module
+                            .get([current_x, current_y + y_extent $(, $extra)*]);
+                        let ne_value = self.source_module.get([
+                            current_x + x_extent,
+                            current_y + y_extent
+                            $(, $extra)*
+                        ]);
+
+                        let x_blend = 1.0 - ((current_x - x_bounds.0) / x_extent);
+                        let y_blend = 1.0 - ((current_y - y_bounds.0) / y_extent);
+
+                        let y0 = interpolate::linear(sw_value, se_value, x_blend);
+                        let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+                        interpolate::linear(y0, y1, y_blend)
+                    } else {
+                        self.source_module.get([current_x, current_y $(, $extra)*])
+                    };
+
+                    if self.track_range {
+                        min = min.min(final_value);
+                        max = max.max(final_value);
+                    }
+
+                    map[(x, y)] = final_value;
+                });
+
+                if self.track_range && min <= max {
+                    map.set_value_range(Some((min, max)));
+                }
+            }
+        }
+    };
+}
+
+impl_plane_build_into!(2);
+impl_plane_build_into!(3, 0.0);
+impl_plane_build_into!(4, 0.0, 0.5);
+
 impl<SourceModule> NoiseMapBuilder<SourceModule> for PlaneMapBuilder<SourceModule, 2>
 where
     SourceModule: NoiseFn<f64, 2>,
@@ -219,41 +525,7 @@ where
         let (width, height) = self.size;
 
         let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y]);
-                    let se_value = self.source_module.get([current_x + x_extent, current_y]);
-                    let nw_value = self.source_module.get([current_x, current_y + y_extent]);
-                    let ne_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y + y_extent]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        self.fill(&mut result_map);
 
         result_map
     }
@@ -285,45 +557,7 @@ where
         let (width, height) = self.size;
 
         let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        self.fill(&mut result_map);
 
         result_map
     }
@@ -338,6 +572,9 @@ where
             is_seamless: false,
             x_bounds: (-1.0, 1.0),
             y_bounds: (-1.0, 1.0),
+            world_grid: None,
+            track_range: false,
+            tile_size: None,
             size: (100, 100),
             source_module: NoiseFnWrapper { source_fn },
         }
@@ -359,41 +596,7 @@ where
         let (width, height) = self.size;
 
         let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y]);
-                    let se_value = self.source_module.get([current_x + x_extent, current_y]);
-                    let nw_value = self.source_module.get([current_x, current_y + y_extent]);
-                    let ne_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y + y_extent]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        self.fill(&mut result_map);
 
         result_map
     }
@@ -407,45 +610,7 @@ where
         let (width, height) = self.size;
 
         let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        self.fill(&mut result_map);
 
         result_map
     }
@@ -459,48 +624,7 @@ where
         let (width, height) = self.size;
 
         let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0, 0.5]);
-                    let se_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y, 0.0, 0.5]);
-                    let nw_value =
-                        self.source_module
-                            .get([current_x, current_y + y_extent, 0.0, 0.5]);
-                    let ne_value = self.source_module.get([
-                        current_x + x_extent,
-                        current_y + y_extent,
-                        0.0,
-                        0.5,
-                    ]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0, 0.5])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        self.fill(&mut result_map);
 
         result_map
     }
@@ -513,6 +637,7 @@ where
 {
     latitude_bounds: (f64, f64),
     longitude_bounds: (f64, f64),
+    tile_size: Option<usize>,
     size: (usize, usize),
     source_module: SourceModule,
 }
@@ -525,6 +650,7 @@ where
         SphereMapBuilder {
             latitude_bounds: (-1.0, 1.0),
             longitude_bounds: (-1.0, 1.0),
+            tile_size: None,
             size: (100, 100),
             source_module,
         }
@@ -558,6 +684,12 @@ where
         }
     }
 
+    /// Sets the size of the square tiles the map is built in, or `None` to
+    /// build it row by row, the default. Tiling never changes the output.
+    pub fn set_tile_size(self, tile_size: Option<usize>) -> Self {
+        SphereMapBuilder { tile_size, ..self }
+    }
+
     pub fn latitude_bounds(&self) -> (f64, f64) {
         self.latitude_bounds
     }
@@ -565,6 +697,10 @@ where
     pub fn longitude_bounds(&self) -> (f64, f64) {
         self.longitude_bounds
     }
+
+    pub fn tile_size(&self) -> Option<usize> {
+        self.tile_size
+    }
 }
 
 impl<SourceModule> NoiseMapBuilder<SourceModule> for SphereMapBuilder<SourceModule>
@@ -600,17 +736,14 @@ where
         let x_step = lon_extent / width as f64;
         let y_step = lat_extent / height as f64;
 
-        for y in 0..height {
+        for_each_pixel(self.size, self.tile_size, |x, y| {
             let current_lat = self.latitude_bounds.0 + y_step * y as f64;
+            let current_lon = self.longitude_bounds.0 + x_step * x as f64;
 
-            for x in 0..width {
-                let current_lon = self.longitude_bounds.0 + x_step * x as f64;
+            let point = lat_lon_to_xyz(current_lat, current_lon);
 
-                let point = lat_lon_to_xyz(current_lat, current_lon);
-
-                result_map[(x, y)] = self.source_module.get(point);
-            }
-        }
+            result_map[(x, y)] = self.source_module.get(point);
+        });
 
         result_map
     }
@@ -624,3 +757,149 @@ fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
 
     [x, y, z]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverted_bounds_are_rejected() {
+        assert_eq!(
+            Err(NoiseError::InvalidBounds {
+                min: 1.0,
+                max: -1.0
+            }),
+            Bounds::new(1.0, -1.0)
+        );
+        assert!(Bounds::new(0.5, 0.5).is_err());
+        assert!(Bounds::new(f64::NAN, 1.0).is_err());
+        assert!(Bounds::try_from((-2.0, 3.0)).is_ok());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid bounds")]
+    fn inverted_tuple_bounds_panic_in_debug_builds() {
+        PlaneMapBuilder::new_fn(|point: [f64; 2]| point[0]).set_x_bounds(1.0, -1.0);
+    }
+
+    #[test]
+    fn bounds_sample_expected_corners() {
+        let x_bounds = Bounds::new(-2.0, 2.0).unwrap();
+        let y_bounds = Bounds::new(1.0, 3.0).unwrap();
+
+        let x_map = PlaneMapBuilder::new_fn(|point: [f64; 2]| point[0])
+            .set_size(4, 4)
+            .set_bounds(x_bounds, y_bounds)
+            .build();
+        let y_map = PlaneMapBuilder::new_fn(|point: [f64; 2]| point[1])
+            .set_size(4, 4)
+            .set_bounds(x_bounds, y_bounds)
+            .build();
+
+        assert_eq!(-2.0, x_map[(0, 0)]);
+        assert_eq!(1.0, x_map[(3, 0)]);
+        assert_eq!(1.0, y_map[(0, 0)]);
+        assert_eq!(2.5, y_map[(0, 3)]);
+    }
+
+    #[test]
+    fn overlapping_world_windows_agree() {
+        let source = crate::Perlin::new(7);
+        let spacing = 0.037;
+
+        let first = PlaneMapBuilder::<_, 2>::new(source)
+            .set_size(32, 24)
+            .set_world_grid((-10, 5), spacing)
+            .build();
+        let second = PlaneMapBuilder::<_, 2>::new(source)
+            .set_size(40, 30)
+            .set_world_grid((3, 11), spacing)
+            .build();
+
+        // The second window starts 13 columns and 6 rows into the first one.
+        for y in 0..18 {
+            for x in 0..19 {
+                assert_eq!(first[(x + 13, y + 6)], second[(x, y)]);
+            }
+        }
+    }
+
+    #[test]
+    fn world_grid_reports_covered_bounds() {
+        let builder = PlaneMapBuilder::new_fn(|point: [f64; 2]| point[0])
+            .set_size(8, 4)
+            .set_world_grid((-4, 2), 0.5);
+
+        assert_eq!((-2.0, 2.0), builder.x_bounds());
+        assert_eq!((1.0, 3.0), builder.y_bounds());
+        assert_eq!(-2.0, builder.build()[(0, 0)]);
+    }
+
+    #[test]
+    fn build_into_reuses_map() {
+        let builder = PlaneMapBuilder::<_, 2>::new(crate::Perlin::new(3))
+            .set_size(16, 12)
+            .set_is_seamless(true);
+        let expected = builder.build();
+
+        let mut map = NoiseMap::new(16, 12);
+        let buffer = map.iter().as_slice().as_ptr();
+        for _ in 0..3 {
+            builder.build_into(&mut map).unwrap();
+            assert_eq!(buffer, map.iter().as_slice().as_ptr());
+        }
+        assert!(expected.iter().eq(map.iter()));
+
+        assert_eq!(
+            Err(NoiseError::SizeMismatch {
+                expected: (16, 12),
+                found: (12, 16)
+            }),
+            builder.build_into(&mut NoiseMap::new(12, 16))
+        );
+    }
+
+    #[test]
+    fn tracked_range_matches_scan() {
+        let builder = PlaneMapBuilder::<_, 2>::new(crate::Perlin::new(11)).set_size(24, 20);
+        assert_eq!(None, builder.build().value_range());
+
+        let mut map = builder.set_track_range(true).build();
+        let min = map.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = map.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(Some((min, max)), map.value_range());
+
+        map[(0, 0)] = 2.0;
+        assert_eq!(None, map.value_range());
+    }
+
+    #[test]
+    fn tiled_build_matches_row_major() {
+        let source = crate::Fbm::<crate::Perlin>::new(5);
+
+        let plane = PlaneMapBuilder::<_, 2>::new(&source)
+            .set_size(70, 45)
+            .set_is_seamless(true)
+            .set_track_range(true);
+        let cylinder = CylinderMapBuilder::new(&source).set_size(37, 29);
+        let sphere = SphereMapBuilder::new(&source)
+            .set_size(33, 50)
+            .set_bounds(-60.0, 60.0, -120.0, 90.0);
+
+        let (plane_map, cylinder_map, sphere_map) =
+            (plane.build(), cylinder.build(), sphere.build());
+
+        for &tile_size in &[0, 1, 7, 16, 64, 100] {
+            let tiled = plane.clone().set_tile_size(Some(tile_size)).build();
+            assert!(plane_map.iter().eq(tiled.iter()));
+            assert_eq!(plane_map.value_range(), tiled.value_range());
+
+            let tiled = cylinder.clone().set_tile_size(Some(tile_size)).build();
+            assert!(cylinder_map.iter().eq(tiled.iter()));
+
+            let tiled = sphere.clone().set_tile_size(Some(tile_size)).build();
+            assert!(sphere_map.iter().eq(tiled.iter()));
+        }
+    }
+}
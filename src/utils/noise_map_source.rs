@@ -0,0 +1,222 @@
+use crate::{
+    math::interpolate,
+    noise_fns::{Add, NoiseFn, ScaleBias},
+    utils::noise_map::{EdgeMode, NoiseMap},
+};
+
+/// A noise function that resamples a precomputed [`NoiseMap`].
+///
+/// This makes it possible to build an expensive map once and feed it into
+/// further noise functions without recomputing its source. The map is assumed
+/// to have been built over `x_bounds` and `y_bounds` by [`PlaneMapBuilder`],
+/// so each stored value is returned exactly at the position it was sampled
+/// at, and values in between are interpolated bilinearly. Positions outside
+/// the map are resolved with the [`EdgeMode`], which clamps them to its edges
+/// by default. Tileable maps should use [`EdgeMode::Wrap`], so that positions
+/// between the last sample and the upper bound blend back into the first
+/// sample.
+///
+/// [`NoiseMap`]: crate::utils::NoiseMap
+/// [`PlaneMapBuilder`]: crate::utils::PlaneMapBuilder
+#[derive(Debug, Clone)]
+pub struct NoiseMapSource {
+    map: NoiseMap,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    edge_mode: EdgeMode,
+    value_range: Option<(f64, f64)>,
+}
+
+impl NoiseMapSource {
+    pub fn new(map: NoiseMap, x_bounds: (f64, f64), y_bounds: (f64, f64)) -> Self {
+        let (width, height) = map.size();
+
+        let mut value_range = None;
+        for y in 0..height {
+            for x in 0..width {
+                let value = map[(x, y)];
+                let (min, max) = value_range.unwrap_or((value, value));
+                value_range = Some((value.min(min), value.max(max)));
+            }
+        }
+
+        Self {
+            map,
+            x_bounds,
+            y_bounds,
+            edge_mode: EdgeMode::Clamp,
+            value_range,
+        }
+    }
+
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self { edge_mode, ..self }
+    }
+
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    pub fn map(&self) -> &NoiseMap {
+        &self.map
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+}
+
+/// Adds procedural detail on top of an imported heightmap.
+///
+/// The returned noise function outputs the interpolated value of `base` plus
+/// the output of `detail` scaled by `weight`. To use real-world elevation data,
+/// load it into a [`NoiseMap`] scaled to roughly `[-1, 1]`, wrap it in a
+/// [`NoiseMapSource`] covering the area the data was taken from, and pass a
+/// generator such as `Fbm` with a frequency high enough to add features smaller
+/// than the spacing of the imported samples. A weight of 0.0 returns the base
+/// map unchanged.
+pub fn overlay_detail<Detail>(
+    base: NoiseMapSource,
+    detail: Detail,
+    weight: f64,
+) -> Add<f64, NoiseMapSource, ScaleBias<f64, Detail, 2>, 2>
+where
+    Detail: NoiseFn<f64, 2>,
+{
+    Add::new(base, ScaleBias::new(detail).set_scale(weight))
+}
+
+/// Converts `value` into a fractional index into an axis of `size` samples
+/// spread over `bounds`, resolving the two neighbouring samples with
+/// `edge_mode`.
+#[inline]
+fn grid_position(
+    value: f64,
+    bounds: (f64, f64),
+    size: usize,
+    edge_mode: EdgeMode,
+) -> (usize, usize, f64) {
+    let step = (bounds.1 - bounds.0) / size as f64;
+    let position = (value - bounds.0) / step;
+
+    let floor = position.floor();
+    let index = floor as isize;
+
+    (
+        edge_mode.resolve(index, size),
+        edge_mode.resolve(index.saturating_add(1), size),
+        position - floor,
+    )
+}
+
+impl NoiseFn<f64, 2> for NoiseMapSource {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let (width, height) = self.map.size();
+        if width == 0 || height == 0 {
+            return self.map.border_value();
+        }
+
+        let (x0, x1, x_alpha) = grid_position(point[0], self.x_bounds, width, self.edge_mode);
+        let (y0, y1, y_alpha) = grid_position(point[1], self.y_bounds, height, self.edge_mode);
+
+        let lower = interpolate::linear(self.map[(x0, y0)], self.map[(x1, y0)], x_alpha);
+        let upper = interpolate::linear(self.map[(x0, y1)], self.map[(x1, y1)], x_alpha);
+
+        interpolate::linear(lower, upper, y_alpha)
+    }
+
+    fn output_range(&self) -> Option<(f64, f64)> {
+        // Bilinear interpolation never leaves the range of the stored values.
+        self.value_range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        utils::{NoiseMapBuilder, PlaneMapBuilder},
+        Fbm, Perlin,
+    };
+
+    #[test]
+    fn grid_points_return_stored_values() {
+        let map = PlaneMapBuilder::<_, 2>::new(Perlin::new(3))
+            .set_size(16, 8)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(0.0, 1.0)
+            .build();
+        let source = NoiseMapSource::new(map.clone(), (-2.0, 2.0), (0.0, 1.0));
+
+        for y in 0..8 {
+            for x in 0..16 {
+                let point = [-2.0 + 0.25 * x as f64, 0.125 * y as f64];
+                assert_eq!(map[(x, y)], source.get(point));
+            }
+        }
+    }
+
+    #[test]
+    fn outside_positions_clamp_to_edges() {
+        let mut map = NoiseMap::new(2, 2);
+        map[(0, 0)] = -1.0;
+        map[(1, 0)] = 1.0;
+        map[(0, 1)] = 0.5;
+        map[(1, 1)] = 0.25;
+        let source = NoiseMapSource::new(map, (0.0, 2.0), (0.0, 2.0));
+
+        assert_eq!(-1.0, source.get([-5.0, -5.0]));
+        assert_eq!(0.25, source.get([5.0, 5.0]));
+        assert_eq!(0.0, source.get([0.5, 0.0]));
+        assert_eq!(Some((-1.0, 1.0)), source.output_range());
+    }
+
+    #[test]
+    fn edge_modes_blend_past_last_sample() {
+        let mut map = NoiseMap::new(4, 1);
+        map[(0, 0)] = 1.0;
+        map[(3, 0)] = -1.0;
+        let source = NoiseMapSource::new(map, (0.0, 4.0), (0.0, 1.0));
+
+        // Halfway between the last sample and the upper bound.
+        let point = [3.5, 0.0];
+
+        assert_eq!(-1.0, source.clone().get(point));
+        assert_eq!(0.0, source.clone().set_edge_mode(EdgeMode::Wrap).get(point));
+        assert_eq!(
+            -1.0,
+            source.clone().set_edge_mode(EdgeMode::Mirror).get(point)
+        );
+
+        // Exactly on the upper bound, which is the first sample of the next
+        // tile when wrapping.
+        let point = [4.0, 0.0];
+        assert_eq!(1.0, source.clone().set_edge_mode(EdgeMode::Wrap).get(point));
+        assert_eq!(-1.0, source.set_edge_mode(EdgeMode::Mirror).get(point));
+    }
+
+    #[test]
+    fn zero_weight_overlay_returns_base_exactly() {
+        let map = PlaneMapBuilder::<_, 2>::new(Perlin::new(3))
+            .set_size(8, 8)
+            .set_x_bounds(0.0, 1.0)
+            .set_y_bounds(0.0, 1.0)
+            .build();
+        let base = NoiseMapSource::new(map.clone(), (0.0, 1.0), (0.0, 1.0));
+        let detail = Fbm::<Perlin>::new(5);
+        let unchanged = overlay_detail(base.clone(), detail.clone(), 0.0);
+        let detailed = overlay_detail(base, detail.clone(), 0.25);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let point = [0.125 * x as f64, 0.125 * y as f64];
+                assert_eq!(map[(x, y)], unchanged.get(point));
+                assert_eq!(map[(x, y)] + 0.25 * detail.get(point), detailed.get(point));
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::noise_fns::NoiseFn;
+use alloc::vec::Vec;
+
+/// Samples `source` along a polyline and returns the profile of the values.
+///
+/// Each segment between consecutive `points` is divided into `subdivisions`
+/// equal steps, and `source` is sampled at the start of every step and at the
+/// last point, so the profile holds `(points.len() - 1) * subdivisions + 1`
+/// values that start at the first point and end at the last one. A single
+/// point is sampled once, and an empty path returns an empty profile. A
+/// subdivision count of zero is treated as one.
+///
+/// This suits carving paths such as rivers and roads, where the noise along
+/// the path decides its depth or width.
+pub fn sample_along_path<Source, const DIM: usize>(
+    source: &Source,
+    points: &[[f64; DIM]],
+    subdivisions: usize,
+) -> Vec<f64>
+where
+    Source: NoiseFn<f64, DIM> + ?Sized,
+{
+    let subdivisions = subdivisions.max(1);
+
+    let last = match points.last() {
+        Some(last) => *last,
+        None => return Vec::new(),
+    };
+
+    let mut profile = Vec::with_capacity((points.len() - 1) * subdivisions + 1);
+    for segment in points.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+
+        for step in 0..subdivisions {
+            let t = step as f64 / subdivisions as f64;
+
+            let mut position = start;
+            for (position, end) in position.iter_mut().zip(&end) {
+                *position += (end - *position) * t;
+            }
+
+            profile.push(source.get(position));
+        }
+    }
+    profile.push(source.get(last));
+
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn straight_segment_matches_direct_samples() {
+        let perlin = Perlin::new(2);
+        let (start, end) = ([-1.5, 0.25, 2.0], [3.0, -0.75, 0.5]);
+
+        let profile = sample_along_path(&perlin, &[start, end], 8);
+
+        assert_eq!(9, profile.len());
+        for (step, value) in profile.iter().enumerate() {
+            let t = step as f64 / 8.0;
+            let position = [
+                start[0] + (end[0] - start[0]) * t,
+                start[1] + (end[1] - start[1]) * t,
+                start[2] + (end[2] - start[2]) * t,
+            ];
+
+            assert_eq!(perlin.get(position), *value);
+        }
+    }
+
+    #[test]
+    fn polyline_visits_every_point() {
+        let perlin = Perlin::new(2);
+        let points = [[0.1, 0.2], [1.3, -0.4], [2.2, 0.9]];
+
+        let profile = sample_along_path(&perlin, &points, 5);
+
+        assert_eq!(11, profile.len());
+        for (index, point) in points.iter().enumerate() {
+            assert_eq!(perlin.get(*point), profile[index * 5]);
+        }
+        assert_eq!(
+            vec![perlin.get(points[0])],
+            sample_along_path(&perlin, &points[..1], 5)
+        );
+        assert!(sample_along_path::<_, 2>(&perlin, &[], 5).is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::noise_fns::NoiseFn;
+
+/// Three noise functions that drive the red, green and blue channels of a
+/// color texture.
+///
+/// Each channel is sampled independently at the same input value, and is
+/// expected to output values within `[-1, 1]`. To turn the channels into an
+/// image, build a `NoiseMap` from each source and pass the three maps to
+/// `ImageRenderer::render_rgb`, which maps each channel onto `[0, 255]`
+/// without a color gradient.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RgbNoise<Red, Green, Blue> {
+    /// Outputs the value of the red channel.
+    pub red: Red,
+
+    /// Outputs the value of the green channel.
+    pub green: Green,
+
+    /// Outputs the value of the blue channel.
+    pub blue: Blue,
+}
+
+impl<Red, Green, Blue> RgbNoise<Red, Green, Blue> {
+    pub fn new(red: Red, green: Green, blue: Blue) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Returns the `[red, green, blue]` output values at the input value.
+    pub fn get_color<T, const DIM: usize>(&self, point: [T; DIM]) -> [f64; 3]
+    where
+        T: Copy,
+        Red: NoiseFn<T, DIM>,
+        Green: NoiseFn<T, DIM>,
+        Blue: NoiseFn<T, DIM>,
+    {
+        [
+            self.red.get(point),
+            self.green.get(point),
+            self.blue.get(point),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn channels_follow_their_sources() {
+        let red = Perlin::new(1);
+        let green = Perlin::new(2);
+        let rgb = RgbNoise::new(red, green, Constant::new(-0.5));
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.21, i as f64 * -0.13];
+            let color = rgb.get_color(point);
+
+            assert_eq!(red.get(point), color[0]);
+            assert_eq!(green.get(point), color[1]);
+            assert_eq!(-0.5, color[2]);
+        }
+    }
+}
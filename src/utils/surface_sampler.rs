@@ -0,0 +1,158 @@
+use crate::{
+    noise_fns::NoiseFn,
+    utils::{
+        noise_map::NoiseMap,
+        noise_map_builder::{for_each_pixel, NoiseMapBuilder},
+    },
+};
+
+/// Builds a noise map by sampling a 3-dimensional noise function on a
+/// parametric surface.
+///
+/// The surface is a function mapping a `[u, v]` coordinate onto a position in
+/// space. Each value of the map is the output of the source module at the
+/// position of the corresponding `[u, v]` coordinate, with _u_ running along
+/// the _x_ axis of the map and _v_ along the _y_ axis. This generalizes
+/// [`SphereMapBuilder`](crate::utils::SphereMapBuilder) and
+/// [`CylinderMapBuilder`](crate::utils::CylinderMapBuilder) to any surface,
+/// such as a torus.
+#[derive(Debug, Clone)]
+pub struct SurfaceSampler<SourceModule, Surface>
+where
+    SourceModule: NoiseFn<f64, 3>,
+    Surface: Fn([f64; 2]) -> [f64; 3],
+{
+    u_bounds: (f64, f64),
+    v_bounds: (f64, f64),
+    size: (usize, usize),
+    tile_size: Option<usize>,
+    surface: Surface,
+    source_module: SourceModule,
+}
+
+impl<SourceModule, Surface> SurfaceSampler<SourceModule, Surface>
+where
+    SourceModule: NoiseFn<f64, 3>,
+    Surface: Fn([f64; 2]) -> [f64; 3],
+{
+    pub fn new(source_module: SourceModule, surface: Surface) -> Self {
+        SurfaceSampler {
+            u_bounds: (0.0, 1.0),
+            v_bounds: (0.0, 1.0),
+            size: (100, 100),
+            tile_size: None,
+            surface,
+            source_module,
+        }
+    }
+
+    pub fn set_u_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        SurfaceSampler {
+            u_bounds: (lower_bound, upper_bound),
+            ..self
+        }
+    }
+
+    pub fn set_v_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        SurfaceSampler {
+            v_bounds: (lower_bound, upper_bound),
+            ..self
+        }
+    }
+
+    /// Sets the size of the square tiles the map is built in, or `None` to
+    /// build it row by row, the default. Tiling never changes the output.
+    pub fn set_tile_size(self, tile_size: Option<usize>) -> Self {
+        SurfaceSampler { tile_size, ..self }
+    }
+
+    pub fn u_bounds(&self) -> (f64, f64) {
+        self.u_bounds
+    }
+
+    pub fn v_bounds(&self) -> (f64, f64) {
+        self.v_bounds
+    }
+
+    pub fn tile_size(&self) -> Option<usize> {
+        self.tile_size
+    }
+}
+
+impl<SourceModule, Surface> NoiseMapBuilder<SourceModule> for SurfaceSampler<SourceModule, Surface>
+where
+    SourceModule: NoiseFn<f64, 3>,
+    Surface: Fn([f64; 2]) -> [f64; 3],
+{
+    fn set_size(self, width: usize, height: usize) -> Self {
+        SurfaceSampler {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    fn set_source_module(self, source_module: SourceModule) -> Self {
+        SurfaceSampler {
+            source_module,
+            ..self
+        }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn build(&self) -> NoiseMap {
+        let (width, height) = self.size;
+
+        let mut result_map = NoiseMap::new(width, height);
+
+        let u_step = (self.u_bounds.1 - self.u_bounds.0) / width as f64;
+        let v_step = (self.v_bounds.1 - self.v_bounds.0) / height as f64;
+
+        for_each_pixel(self.size, self.tile_size, |x, y| {
+            let u = self.u_bounds.0 + u_step * x as f64;
+            let v = self.v_bounds.0 + v_step * y as f64;
+
+            result_map[(x, y)] = self.source_module.get((self.surface)([u, v]));
+        });
+
+        result_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils::SphereMapBuilder, Perlin};
+
+    #[test]
+    fn sphere_surface_matches_sphere_builder() {
+        let perlin = Perlin::new(6);
+
+        let expected = SphereMapBuilder::new(perlin)
+            .set_size(32, 16)
+            .set_bounds(-60.0, 75.0, -170.0, 120.0)
+            .build();
+        let sampled = SurfaceSampler::new(perlin, |[lon, lat]: [f64; 2]| {
+            let r = lat.to_radians().cos();
+            [
+                r * lon.to_radians().cos(),
+                lat.to_radians().sin(),
+                r * lon.to_radians().sin(),
+            ]
+        })
+        .set_size(32, 16)
+        .set_u_bounds(-170.0, 120.0)
+        .set_v_bounds(-60.0, 75.0);
+
+        for &tile_size in &[None, Some(5)] {
+            let sampled = sampled.clone().set_tile_size(tile_size).build();
+
+            assert_eq!(expected.size(), sampled.size());
+            for (expected, sampled) in expected.iter().zip(sampled.iter()) {
+                assert_eq!(expected, sampled);
+            }
+        }
+    }
+}
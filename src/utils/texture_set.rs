@@ -0,0 +1,198 @@
+use crate::{
+    noise_fns::NoiseFn,
+    utils::{
+        color_gradient::ColorGradient,
+        noise_image::NoiseImage,
+        noise_map::{EdgeMode, NoiseMap},
+        noise_map_builder::{NoiseMapBuilder, PlaneMapBuilder},
+    },
+};
+
+/// Matching height, color and normal maps that tile seamlessly, built by
+/// [`TextureSetBuilder`].
+#[derive(Debug, Clone)]
+pub struct TextureSet {
+    /// Output values of the source module.
+    pub height: NoiseMap,
+
+    /// Height map rendered through the color gradient.
+    pub color: NoiseImage,
+
+    /// Tangent-space normals of the height map, with the _x_, _y_ and _z_
+    /// components mapped from `[-1, 1]` onto the red, green and blue channels.
+    pub normal: NoiseImage,
+}
+
+/// Builds a set of seamlessly tiling textures from a 3-dimensional noise
+/// function: a height map, a color map and a normal map.
+///
+/// The height map is built with a seamless [`PlaneMapBuilder`] over a square
+/// of `period` units. The color map renders it through a color gradient, and
+/// the normal map is derived from its slopes, wrapping around the edges, so all
+/// three textures line up and tile over the same period.
+#[derive(Debug, Clone)]
+pub struct TextureSetBuilder<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 3>,
+{
+    gradient: ColorGradient,
+    period: f64,
+    normal_strength: f64,
+    size: (usize, usize),
+    source_module: SourceModule,
+}
+
+impl<SourceModule> TextureSetBuilder<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 3>,
+{
+    pub fn new(source_module: SourceModule) -> Self {
+        TextureSetBuilder {
+            gradient: ColorGradient::new(),
+            period: 1.0,
+            normal_strength: 1.0,
+            size: (100, 100),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        TextureSetBuilder {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn set_gradient(self, gradient: ColorGradient) -> Self {
+        TextureSetBuilder { gradient, ..self }
+    }
+
+    /// Sets the length of the square of input space covered by the textures,
+    /// which they repeat after. The default is 1.0.
+    pub fn set_period(self, period: f64) -> Self {
+        TextureSetBuilder { period, ..self }
+    }
+
+    /// Sets the factor applied to the slopes of the height map when deriving
+    /// the normals. Larger values give a more pronounced relief. The default
+    /// is 1.0.
+    pub fn set_normal_strength(self, normal_strength: f64) -> Self {
+        TextureSetBuilder {
+            normal_strength,
+            ..self
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    pub fn build(&self) -> TextureSet {
+        let (width, height) = self.size;
+
+        let height_map = PlaneMapBuilder::<_, 3>::new(&self.source_module)
+            .set_size(width, height)
+            .set_is_seamless(true)
+            .set_x_bounds(0.0, self.period)
+            .set_y_bounds(0.0, self.period)
+            .build();
+
+        let mut color = NoiseImage::new(width, height);
+        let mut normal = NoiseImage::new(width, height);
+
+        // Slopes are measured in input units, so the relief does not depend on
+        // the resolution.
+        let x_scale = self.normal_strength * width as f64 / (2.0 * self.period);
+        let y_scale = self.normal_strength * height as f64 / (2.0 * self.period);
+
+        for y in 0..height {
+            for x in 0..width {
+                color[(x, y)] = self.gradient.get_color(height_map[(x, y)]);
+
+                let sample = |dx: isize, dy: isize| {
+                    height_map.get_value_with_edge_mode(
+                        x as isize + dx,
+                        y as isize + dy,
+                        EdgeMode::Wrap,
+                    )
+                };
+                let slope_x = (sample(1, 0) - sample(-1, 0)) * x_scale;
+                let slope_y = (sample(0, 1) - sample(0, -1)) * y_scale;
+
+                normal[(x, y)] = encode_normal([-slope_x, -slope_y, 1.0]);
+            }
+        }
+
+        TextureSet {
+            height: height_map,
+            color,
+            normal,
+        }
+    }
+}
+
+/// Normalizes `normal` and maps its components from `[-1, 1]` onto `[0, 255]`.
+fn encode_normal(normal: [f64; 3]) -> [u8; 4] {
+    let length = normal.iter().map(|c| c * c).sum::<f64>().sqrt();
+    let channel = |c: f64| ((c / length * 0.5 + 0.5) * 255.0).round() as u8;
+
+    [
+        channel(normal[0]),
+        channel(normal[1]),
+        channel(normal[2]),
+        255,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils::GradientPreset, Fbm, Perlin};
+
+    /// Asserts that stepping across the wrap-around is comparable to the
+    /// largest step between neighbouring values within the texture, rather
+    /// than a jump between unrelated values.
+    fn assert_tiles(size: (usize, usize), value: impl Fn(usize, usize) -> f64) {
+        let (width, height) = size;
+        let step = |a: f64, b: f64| (a - b).abs();
+
+        for y in 0..height {
+            let interior = (1..width)
+                .map(|x| step(value(x - 1, y), value(x, y)))
+                .fold(0.0, f64::max);
+            assert!(step(value(width - 1, y), value(0, y)) <= interior * 1.5 + 1.0);
+        }
+
+        for x in 0..width {
+            let interior = (1..height)
+                .map(|y| step(value(x, y - 1), value(x, y)))
+                .fold(0.0, f64::max);
+            assert!(step(value(x, height - 1), value(x, 0)) <= interior * 1.5 + 1.0);
+        }
+    }
+
+    #[test]
+    fn texture_set_tiles_seamlessly() {
+        let set = TextureSetBuilder::new(Fbm::<Perlin>::new(4))
+            .set_size(48, 32)
+            .set_period(3.0)
+            .set_gradient(ColorGradient::preset(GradientPreset::Terrain))
+            .build();
+
+        let size = set.height.size();
+        assert_eq!((48, 32), size);
+        assert_eq!(size, set.color.size());
+        assert_eq!(size, set.normal.size());
+
+        // The height map is compared in the same units as the 8-bit channels.
+        assert_tiles(size, |x, y| set.height[(x, y)] * 127.5);
+        for channel in 0..3 {
+            assert_tiles(size, |x, y| set.color[(x, y)][channel] as f64);
+            assert_tiles(size, |x, y| set.normal[(x, y)][channel] as f64);
+        }
+    }
+}
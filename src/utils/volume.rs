@@ -0,0 +1,128 @@
+use crate::noise_fns::NoiseFn;
+use alloc::vec::Vec;
+
+/// Samples `source` on a dense 3D grid and returns the values in a flat buffer.
+///
+/// The grid spans from `bounds_min` to `bounds_max` with `size[0] * size[1] *
+/// size[2]` samples, positioned the same way `PlaneMapBuilder` positions the
+/// samples of a map: the first sample along each axis is at its lower bound,
+/// and the upper bound itself is not sampled.
+///
+/// The buffer is ordered with _x_ varying fastest and _z_ slowest, so the
+/// sample at grid position `(x, y, z)` is found at index
+/// `x + size[0] * (y + size[1] * z)`.
+///
+/// When the `rayon` feature is enabled, the _z_ slices are sampled in parallel,
+/// which requires `source` to be `Sync`.
+#[cfg(not(feature = "rayon"))]
+pub fn build_volume<Source>(
+    source: &Source,
+    bounds_min: [f64; 3],
+    bounds_max: [f64; 3],
+    size: [usize; 3],
+) -> Vec<f64>
+where
+    Source: NoiseFn<f64, 3> + ?Sized,
+{
+    let mut buffer = alloc::vec![0.0; size[0] * size[1] * size[2]];
+    let slice_len = size[0] * size[1];
+
+    if slice_len > 0 {
+        buffer
+            .chunks_mut(slice_len)
+            .enumerate()
+            .for_each(|(z, slice)| fill_slice(source, bounds_min, bounds_max, size, z, slice));
+    }
+
+    buffer
+}
+
+/// Samples `source` on a dense 3D grid and returns the values in a flat buffer.
+///
+/// The grid spans from `bounds_min` to `bounds_max` with `size[0] * size[1] *
+/// size[2]` samples, positioned the same way `PlaneMapBuilder` positions the
+/// samples of a map: the first sample along each axis is at its lower bound,
+/// and the upper bound itself is not sampled.
+///
+/// The buffer is ordered with _x_ varying fastest and _z_ slowest, so the
+/// sample at grid position `(x, y, z)` is found at index
+/// `x + size[0] * (y + size[1] * z)`.
+///
+/// When the `rayon` feature is enabled, the _z_ slices are sampled in parallel,
+/// which requires `source` to be `Sync`.
+#[cfg(feature = "rayon")]
+pub fn build_volume<Source>(
+    source: &Source,
+    bounds_min: [f64; 3],
+    bounds_max: [f64; 3],
+    size: [usize; 3],
+) -> Vec<f64>
+where
+    Source: NoiseFn<f64, 3> + Sync + ?Sized,
+{
+    use rayon::prelude::*;
+
+    let mut buffer = alloc::vec![0.0; size[0] * size[1] * size[2]];
+    let slice_len = size[0] * size[1];
+
+    if slice_len > 0 {
+        buffer
+            .par_chunks_mut(slice_len)
+            .enumerate()
+            .for_each(|(z, slice)| fill_slice(source, bounds_min, bounds_max, size, z, slice));
+    }
+
+    buffer
+}
+
+/// Fills `slice` with the samples of the _z_ slice at grid position `z`.
+fn fill_slice<Source>(
+    source: &Source,
+    bounds_min: [f64; 3],
+    bounds_max: [f64; 3],
+    size: [usize; 3],
+    z: usize,
+    slice: &mut [f64],
+) where
+    Source: NoiseFn<f64, 3> + ?Sized,
+{
+    let step = |axis: usize| (bounds_max[axis] - bounds_min[axis]) / size[axis] as f64;
+    let (x_step, y_step, z_step) = (step(0), step(1), step(2));
+
+    let current_z = bounds_min[2] + z_step * z as f64;
+
+    for (y, row) in slice.chunks_mut(size[0]).enumerate() {
+        let current_y = bounds_min[1] + y_step * y as f64;
+
+        for (x, value) in row.iter_mut().enumerate() {
+            let current_x = bounds_min[0] + x_step * x as f64;
+
+            *value = source.get([current_x, current_y, current_z]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn voxel_matches_direct_sample() {
+        let perlin = Perlin::new(8);
+        let size = [5, 4, 3];
+        let volume = build_volume(&perlin, [-1.0, 0.0, 2.0], [1.5, 2.0, 3.5], size);
+
+        assert_eq!(volume.len(), 60);
+
+        let (x, y, z) = (3, 2, 1);
+        let point = [-1.0 + 0.5 * x as f64, 0.5 * y as f64, 2.0 + 0.5 * z as f64];
+        assert_eq!(volume[x + size[0] * (y + size[1] * z)], perlin.get(point));
+    }
+
+    #[test]
+    fn empty_volume_is_empty() {
+        let volume = build_volume(&Perlin::new(8), [0.0; 3], [1.0; 3], [0, 4, 4]);
+        assert!(volume.is_empty());
+    }
+}